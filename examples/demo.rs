@@ -0,0 +1,8 @@
+use secp256k1::Point;
+
+fn main() {
+    // Basic example usage and testing
+    let gen = Point::generator();
+    println!("Generator Point: {:?}", gen);
+    println!("Is generator on curve: {}", gen.is_on_curve());
+}
@@ -0,0 +1,36 @@
+// Rough timing of a tight loop of field multiplications and squarings:
+// cargo run --release --example field_bench
+use std::hint::black_box;
+use std::time::Instant;
+
+use secp256k1::{FieldElement, GENERATOR_X, GENERATOR_Y};
+
+const ITERATIONS: u32 = 1_000_000;
+
+fn main() {
+    let mut a = FieldElement::new(GENERATOR_X);
+    let b = FieldElement::new(GENERATOR_Y);
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        a = black_box(a) * b;
+    }
+    let elapsed = start.elapsed();
+    println!("mul:        {:>6.1} ns/op ({:?})", elapsed.as_nanos() as f64 / ITERATIONS as f64, a.to_bytes()[0]);
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        let mut t = black_box(a);
+        t *= b;
+        a = t;
+    }
+    let elapsed = start.elapsed();
+    println!("mul_assign: {:>6.1} ns/op ({:?})", elapsed.as_nanos() as f64 / ITERATIONS as f64, a.to_bytes()[0]);
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        a = black_box(a).square();
+    }
+    let elapsed = start.elapsed();
+    println!("square:     {:>6.1} ns/op ({:?})", elapsed.as_nanos() as f64 / ITERATIONS as f64, a.to_bytes()[0]);
+}
@@ -0,0 +1,148 @@
+// Base58 and Base58Check, the encodings of Bitcoin addresses and WIF keys
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[cfg(feature = "hashes")]
+use crate::hashes::sha256;
+use crate::Error;
+
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+// Base58 as used by Bitcoin: the bytes read as one big-endian number, with
+// each leading zero byte written as a '1'
+pub fn encode(data: &[u8]) -> String {
+    let zeros = data.iter().take_while(|&&b| b == 0).count();
+
+    // Little-endian base-58 digits, built by repeated multiply-and-add
+    let mut digits: Vec<u8> = Vec::with_capacity(data.len() * 138 / 100 + 1);
+    for &byte in &data[zeros..] {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    let mut out = String::with_capacity(zeros + digits.len());
+    out.extend(core::iter::repeat_n('1', zeros));
+    out.extend(digits.iter().rev().map(|&d| ALPHABET[d as usize] as char));
+    out
+}
+
+// Inverse of encode; characters outside the alphabet are rejected
+pub fn decode(s: &str) -> Result<Vec<u8>, Error> {
+    let zeros = s.bytes().take_while(|&c| c == b'1').count();
+
+    // Little-endian base-256 bytes
+    let mut bytes: Vec<u8> = Vec::with_capacity(s.len() * 733 / 1000 + 1);
+    for c in s.bytes().skip(zeros) {
+        let value = ALPHABET.iter().position(|&a| a == c).ok_or(Error::InvalidEncoding)?;
+        let mut carry = value as u32;
+        for byte in bytes.iter_mut() {
+            carry += *byte as u32 * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    let mut out = vec![0u8; zeros];
+    out.extend(bytes.iter().rev());
+    Ok(out)
+}
+
+#[cfg(feature = "hashes")]
+fn checksum(payload: &[u8]) -> [u8; 4] {
+    let hash = sha256(&sha256(payload));
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+// Base58Check: the payload followed by the first four bytes of its double
+// SHA-256, Base58 encoded
+#[cfg(feature = "hashes")]
+pub fn encode_check(payload: &[u8]) -> String {
+    let mut data = payload.to_vec();
+    data.extend_from_slice(&checksum(payload));
+    encode(&data)
+}
+
+// Decode Base58Check and verify the checksum, returning the payload
+#[cfg(feature = "hashes")]
+pub fn decode_check(s: &str) -> Result<Vec<u8>, Error> {
+    let mut data = decode(s)?;
+    if data.len() < 4 {
+        return Err(Error::InvalidEncoding);
+    }
+    let check = data.split_off(data.len() - 4);
+    if check != checksum(&data) {
+        return Err(Error::InvalidChecksum);
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    #[test]
+    fn test_base58_vectors() {
+        // From Bitcoin Core's base58_encode_decode.json
+        let vectors = [
+            ("", ""),
+            ("61", "2g"),
+            ("626262", "a3gV"),
+            ("636363", "aPEr"),
+            ("73696d706c792061206c6f6e6720737472696e67", "2cFupjhnEsSn59qHXstmK2ffpLv2"),
+            ("00eb15231dfceb60925886b67d065299925915aeb172c06647", "1NS17iag9jJgTHD1VXjvLCEnZuQ3rJDE9L"),
+            ("516b6fcd0f", "ABnLTmg"),
+            ("bf4f89001e670274dd", "3SEo3LWLoPntC"),
+            ("572e4794", "3EFU7m"),
+            ("ecac89cad93923c02321", "EJDM8drfXA6uyA"),
+            ("10c8511e", "Rt5zm"),
+            ("00000000000000000000", "1111111111"),
+            (
+                "000111d38e5fc9071ffcd20b4a763cc9ae4f252bb4e48fd66a835e252ada93ff480d6dd43dc62a641155a5",
+                "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz",
+            ),
+        ];
+        for (data, encoded) in vectors {
+            let data = hex::decode(data).unwrap();
+            assert_eq!(encode(&data), encoded);
+            assert_eq!(decode(encoded), Ok(data));
+        }
+    }
+
+    #[test]
+    fn test_base58_rejects_invalid_characters() {
+        for s in ["0", "O", "I", "l", "3SEo3LWLoPntC!", "a3g V"] {
+            assert_eq!(decode(s), Err(Error::InvalidEncoding), "{:?}", s);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "hashes")]
+    fn test_base58check_genesis_address() {
+        // Version 0x00 and the HASH160 of the genesis block's coinbase key
+        let payload = hex::decode("0062e907b15cbf27d5425399ebf6f0fb50ebb88f18").unwrap();
+        assert_eq!(encode_check(&payload), "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa");
+        assert_eq!(decode_check("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa"), Ok(payload));
+
+        assert_eq!(decode_check("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb"), Err(Error::InvalidChecksum));
+        // Fewer than four bytes cannot hold a checksum
+        assert_eq!(decode_check("3EFU"), Err(Error::InvalidEncoding));
+        // Leading zero bytes survive the round trip
+        assert_eq!(decode_check(&encode_check(&[0, 0, 0])), Ok(vec![0, 0, 0]));
+        assert_eq!(decode_check(&encode_check(&[])), Ok(vec![]));
+    }
+}
@@ -0,0 +1,282 @@
+use std::sync::OnceLock;
+
+#[cfg(feature = "hashes")]
+use crate::Error;
+use crate::{FieldElement, JacobianPoint, Point, Scalar};
+
+// Lim-Lee comb for k·G. The 256 scalar bits are read as TEETH rows of
+// SPACING bits; column c gathers bit c of every row into a TEETH-bit digit,
+// and entry j of the table is Σ 2^(i·SPACING)·G over the bits i set in j.
+// Then k·G = Σ 2^c·table[digit(c)], which takes SPACING - 1 doublings and
+// SPACING additions whatever k is.
+//
+// With TEETH = 8 that is 31 doublings and 32 additions, against a table of
+// 255 affine points (16 KiB of coordinates).
+const TEETH: usize = 8;
+const SPACING: usize = 256 / TEETH;
+const ENTRIES: usize = 1 << TEETH;
+
+// Offset point for the accumulator:
+// hash_to_curve(b"comb offset", b"SECP256K1-COMB-V01_XMD:SHA-256_SSWU_RO_").
+// Any point with an unknown discrete log will do; a hash output is one.
+const COMB_OFFSET: Point = Point {
+    x: Some(FieldElement {
+        value: [
+            0xF2777E6BC3B67332,
+            0x1AF9F69492ED561C,
+            0x5252E662AC084B1D,
+            0x1EC329541401883A,
+        ],
+    }),
+    y: Some(FieldElement {
+        value: [
+            0x100477D8A135B516,
+            0xFC8B17354276C872,
+            0x55FCFDB3C683E313,
+            0xD992F334A013C41A,
+        ],
+    }),
+};
+
+// Serialized form, as part of serialize_generator_table: entries 1..ENTRIES
+// as x || y, big-endian
+#[cfg(feature = "hashes")]
+pub(crate) const COMB_BYTES: usize = (ENTRIES - 1) * 64;
+
+static COMB_TABLE: OnceLock<CombTable> = OnceLock::new();
+
+pub(crate) struct CombTable {
+    // entries[j] for j = 1..ENTRIES as affine (x, y); slot 0 is unused
+    entries: Vec<(FieldElement, FieldElement)>,
+    // The accumulator starts at an offset point O so that it is never
+    // infinity; correction = -2^(SPACING - 1)·O removes it at the end
+    offset: JacobianPoint,
+    correction: JacobianPoint,
+}
+
+impl CombTable {
+    fn compute() -> Self {
+        // rows[i] = 2^(i·SPACING)·G
+        let mut rows = Vec::with_capacity(TEETH);
+        let mut row = JacobianPoint::from_affine(&Point::generator());
+        for _ in 0..TEETH {
+            rows.push(row);
+            for _ in 0..SPACING {
+                row = row.double();
+            }
+        }
+
+        // Each entry adds its top row to an entry already computed
+        let mut points = vec![JacobianPoint::infinity(); ENTRIES];
+        for j in 1..ENTRIES {
+            let top = usize::BITS - 1 - j.leading_zeros();
+            points[j] = points[j ^ (1 << top)].add(&rows[top as usize]);
+        }
+        JacobianPoint::normalize_all(&mut points);
+        CombTable::from_entries(points.iter().map(|p| (p.x, p.y)).collect())
+    }
+
+    fn from_entries(entries: Vec<(FieldElement, FieldElement)>) -> Self {
+        let offset = JacobianPoint::from_affine(&COMB_OFFSET);
+        let mut correction = offset;
+        for _ in 0..SPACING - 1 {
+            correction = correction.double();
+        }
+        correction.y = -correction.y;
+
+        CombTable {
+            entries,
+            offset,
+            correction,
+        }
+    }
+
+    #[cfg(feature = "hashes")]
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(COMB_BYTES);
+        for (x, y) in &self.entries[1..] {
+            bytes.extend_from_slice(&x.to_bytes());
+            bytes.extend_from_slice(&y.to_bytes());
+        }
+        bytes
+    }
+
+    // Parse the to_bytes form and check every entry, so a loaded table
+    // computes exactly what a computed one would
+    #[cfg(feature = "hashes")]
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != COMB_BYTES {
+            return Err(Error::InvalidEncoding);
+        }
+        let mut entries = Vec::with_capacity(ENTRIES);
+        entries.push((FieldElement::from(0), FieldElement::from(0)));
+        for chunk in bytes.chunks_exact(64) {
+            let x = FieldElement::from_bytes(chunk[..32].try_into().unwrap())?;
+            let y = FieldElement::from_bytes(chunk[32..].try_into().unwrap())?;
+            Point::from_affine(x, y)?;
+            entries.push((x, y));
+        }
+        let table = CombTable::from_entries(entries);
+        table.validate()?;
+        Ok(table)
+    }
+
+    // Entry 1 must be G, each row entry 2^SPACING times the one below it,
+    // and every other entry its top row plus the rest, as compute builds
+    // them. Jacobian arithmetic only, so no inversions.
+    #[cfg(feature = "hashes")]
+    fn validate(&self) -> Result<(), Error> {
+        let affine = |j: usize| Point {
+            x: Some(self.entries[j].0),
+            y: Some(self.entries[j].1),
+        };
+        let mut row = JacobianPoint::from_affine(&Point::generator());
+        for tooth in 0..TEETH {
+            if !row.eq_affine(&affine(1 << tooth)) {
+                return Err(Error::InvalidEncoding);
+            }
+            for _ in 0..SPACING {
+                row = row.double();
+            }
+        }
+        for j in 3..ENTRIES {
+            let top = 1 << (usize::BITS - 1 - j.leading_zeros());
+            if j == top {
+                continue;
+            }
+            let expected = JacobianPoint::from_affine(&affine(j ^ top)).add(&JacobianPoint::from_affine(&affine(top)));
+            if !expected.eq_affine(&affine(j)) {
+                return Err(Error::InvalidEncoding);
+            }
+        }
+        Ok(())
+    }
+
+    // entries[digit], read by scanning the whole table so the memory access
+    // pattern does not depend on digit. Digit 0 (infinity) yields entry 1;
+    // the caller discards that sum.
+    fn lookup(&self, digit: usize) -> JacobianPoint {
+        let (mut x, mut y) = self.entries[1];
+        for (index, (entry_x, entry_y)) in self.entries.iter().enumerate().skip(2) {
+            let choice = (((index ^ digit) as u32).wrapping_sub(1) >> 31) as u8;
+            x = FieldElement::conditional_select(&x, entry_x, choice);
+            y = FieldElement::conditional_select(&y, entry_y, choice);
+        }
+        JacobianPoint {
+            x,
+            y,
+            z: FieldElement::from(1),
+        }
+    }
+
+    pub(crate) fn mul(&self, scalar: &Scalar) -> Point {
+        let mut acc = self.offset;
+        for column in (0..SPACING).rev() {
+            if column != SPACING - 1 {
+                acc = acc.double();
+            }
+            let mut digit = 0;
+            for tooth in 0..TEETH {
+                digit |= (scalar.bit(tooth * SPACING + column) as usize) << tooth;
+            }
+            // Always add, then keep the old value if the digit was zero
+            let sum = acc.add(&self.lookup(digit));
+            let zero = ((digit as u32).wrapping_sub(1) >> 31) as u8;
+            acc = JacobianPoint {
+                x: FieldElement::conditional_select(&sum.x, &acc.x, zero),
+                y: FieldElement::conditional_select(&sum.y, &acc.y, zero),
+                z: FieldElement::conditional_select(&sum.z, &acc.z, zero),
+            };
+        }
+        acc.add(&self.correction).to_affine()
+    }
+}
+
+pub(crate) fn global_comb_table() -> &'static CombTable {
+    COMB_TABLE.get_or_init(CombTable::compute)
+}
+
+// Install a comb table parsed by CombTable::from_bytes, keeping any table
+// already in use; a second valid table is identical to it
+#[cfg(feature = "hashes")]
+pub(crate) fn install_comb_table(table: CombTable) {
+    let _ = COMB_TABLE.set(table);
+}
+
+impl Point {
+    // k·G by the fixed-base comb above, table computed on first use unless
+    // one was installed with load_generator_table. Every
+    // scalar costs the same doublings, additions and full-table lookups, so
+    // the work does not depend on k; the Jacobian formulas only branch for
+    // an input at infinity or equal inputs, which the offset point makes
+    // unreachable short of k = 0 or a discrete log of the offset.
+    pub fn mul_generator_comb(scalar: &Scalar) -> Point {
+        global_comb_table().mul(scalar)
+    }
+
+    // k·G in constant time, for secret k: key generation and signing
+    // nonces. With std this is the comb; mul_generator_vartime is faster for
+    // public k.
+    pub fn mul_generator(scalar: &Scalar) -> Point {
+        Point::mul_generator_comb(scalar)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "hashes")]
+    fn test_comb_offset() {
+        let dst = b"SECP256K1-COMB-V01_XMD:SHA-256_SSWU_RO_";
+        assert_eq!(crate::hash_to_curve(b"comb offset", dst), COMB_OFFSET);
+        assert!(COMB_OFFSET.is_on_curve());
+    }
+
+    #[test]
+    fn test_comb_matches_scalar_mul() {
+        let mut n_minus_one = -Scalar::from(1);
+        let scalars = [
+            Scalar::from(1),
+            Scalar::from(2),
+            Scalar::from(0xFF),
+            Scalar::new([0, 0, 0, 1]),
+            Scalar::new([
+                0x0123456789ABCDEF,
+                0xFEDCBA9876543210,
+                0xDEADBEEFCAFEBABE,
+                0x7FFFFFFFFFFFFFFF,
+            ]),
+            Scalar::new([u64::MAX, u64::MAX, u64::MAX, u64::MAX]),
+            n_minus_one,
+        ];
+        for k in scalars {
+            assert_eq!(
+                Point::mul_generator_comb(&k),
+                Point::generator().scalar_mul(&k.value),
+                "{:?}",
+                k
+            );
+        }
+        n_minus_one.conditional_negate(1);
+        assert_eq!(Point::mul_generator_comb(&n_minus_one), Point::generator());
+        assert!(Point::mul_generator_comb(&Scalar::from(0)).is_infinity());
+    }
+
+    #[test]
+    fn test_comb_table_entries() {
+        let table = COMB_TABLE.get_or_init(CombTable::compute);
+        assert_eq!(table.entries.len(), ENTRIES);
+        // Entry 1 is G, entry 2 is 2^32·G and entry 3 their sum
+        let g = Point::generator();
+        let row = g.scalar_mul(&[1 << SPACING, 0, 0, 0]);
+        let affine = |(x, y): (FieldElement, FieldElement)| Point {
+            x: Some(x),
+            y: Some(y),
+        };
+        assert_eq!(affine(table.entries[1]), g);
+        assert_eq!(affine(table.entries[2]), row);
+        assert_eq!(affine(table.entries[3]), row.add(&g));
+    }
+}
@@ -0,0 +1,73 @@
+use crate::{FieldElement, Point, CURVE_A, CURVE_B, CURVE_ORDER, FIELD_MODULUS};
+
+// The secp256k1 domain parameters: y² = x³ + a·x + b over the field of
+// order p, with generator G of prime order n and cofactor 1.
+//
+// p and n are returned as limbs rather than as a FieldElement and a Scalar:
+// each is zero in its own ring, so those types cannot hold them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Curve(());
+
+impl Curve {
+    pub fn secp256k1() -> Curve {
+        Curve(())
+    }
+
+    // The group order n, little-endian limbs
+    pub fn order(&self) -> [u64; 4] {
+        CURVE_ORDER
+    }
+
+    // The field prime p, little-endian limbs
+    pub fn prime(&self) -> [u64; 4] {
+        FIELD_MODULUS
+    }
+
+    pub fn a(&self) -> FieldElement {
+        FieldElement::from(CURVE_A)
+    }
+
+    pub fn b(&self) -> FieldElement {
+        FieldElement::new(CURVE_B)
+    }
+
+    pub fn generator(&self) -> Point {
+        Point::generator()
+    }
+
+    pub fn cofactor(&self) -> u64 {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{hex, limbs_from_bytes};
+
+    #[test]
+    fn test_curve_parameters() {
+        // From SEC 2, section 2.4.1
+        let curve = Curve::secp256k1();
+        let n = hex::decode32("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+        let p = hex::decode32("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f");
+        assert_eq!(curve.order(), limbs_from_bytes(&n));
+        assert_eq!(curve.prime(), limbs_from_bytes(&p));
+        assert!(curve.a().is_zero());
+        assert_eq!(curve.b(), FieldElement::from(7));
+
+        let g = curve.generator();
+        assert!(g.is_on_curve());
+        assert_eq!(
+            g.serialize(false),
+            hex::decode(
+                "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+                 483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"
+            )
+            .unwrap()
+        );
+        // n is prime and n·G is the identity, so n is G's order
+        assert!(g.scalar_mul(&curve.order()).is_infinity());
+        assert_eq!(curve.cofactor(), 1);
+    }
+}
@@ -0,0 +1,52 @@
+use crate::hashes::sha256;
+use crate::{Error, PrivateKey, PublicKey};
+
+// ECDH shared secret: SHA-256 of the compressed encoding of d·Q, matching
+// the default hash function of libsecp256k1's secp256k1_ecdh. d is secret,
+// so d·Q takes the constant-time scalar_mul, never the GLV fast path.
+pub fn ecdh(privkey: &PrivateKey, pubkey: &PublicKey) -> Result<[u8; 32], Error> {
+    pubkey.verify_valid()?;
+    let shared = pubkey.as_point().scalar_mul(&privkey.as_scalar().value);
+    // With d in [1, n-1] and Q a curve point this cannot happen, but an
+    // all-zero secret must never be handed out
+    if shared.is_infinity() {
+        return Err(Error::PointAtInfinity);
+    }
+    Ok(sha256(&shared.serialize(true)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    fn key(s: &str) -> PrivateKey {
+        PrivateKey::from_bytes(&hex::decode32(s)).unwrap()
+    }
+
+    #[test]
+    fn test_ecdh_symmetric() {
+        let a = key("c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721");
+        let b = key("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140");
+        let c = key("0000000000000000000000000000000000000000000000000000000000000003");
+
+        let ab = ecdh(&a, &b.public_key()).unwrap();
+        assert_eq!(ab, ecdh(&b, &a.public_key()).unwrap());
+        assert_eq!(ecdh(&a, &c.public_key()), ecdh(&c, &a.public_key()));
+        assert_ne!(ab, ecdh(&a, &c.public_key()).unwrap());
+
+        // Cross-checked against OpenSSL's ECDH x coordinate, 415a9a83...
+        assert_eq!(
+            ecdh(&a, &c.public_key()),
+            Ok(hex::decode32("b447090e821091b0bb3bbc206fc95d423b3dab9f57c3ab60395ab9b92fff5939"))
+        );
+    }
+
+    #[test]
+    fn test_ecdh_hashes_compressed_point() {
+        // d = 1 gives Q itself, so the secret is sha256 of Q's encoding
+        let one = key("0000000000000000000000000000000000000000000000000000000000000001");
+        let pubkey = key("c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721").public_key();
+        assert_eq!(ecdh(&one, &pubkey), Ok(sha256(&pubkey.serialize(true))));
+    }
+}
@@ -0,0 +1,859 @@
+use alloc::vec::Vec;
+
+use rand_core::{CryptoRng, RngCore};
+
+use crate::keys::validate_public_point;
+#[cfg(feature = "hashes")]
+use crate::rfc6979::rfc6979_nonce;
+use crate::{
+    add_limbs, geq_limbs, limbs_from_bytes, multi_scalar_mul_vartime, Error, FieldElement, Point, PublicKey, Scalar,
+    CURVE_ORDER, FIELD_MODULUS,
+};
+
+// n / 2 (rounded down, since n is odd): the largest low-S value
+const HALF_ORDER: [u64; 4] = shr1(&CURVE_ORDER);
+
+// Shift a 256-bit value right by one bit
+const fn shr1(a: &[u64; 4]) -> [u64; 4] {
+    let mut result = [0u64; 4];
+    let mut i = 0;
+    while i < 4 {
+        result[i] = a[i] >> 1;
+        if i < 3 {
+            result[i] |= a[i + 1] << 63;
+        }
+        i += 1;
+    }
+    result
+}
+
+// ECDSA signature (r, s)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Signature {
+    r: Scalar,
+    s: Scalar,
+}
+
+impl Signature {
+    pub fn new(r: Scalar, s: Scalar) -> Self {
+        Signature { r, s }
+    }
+
+    pub fn r(&self) -> &Scalar {
+        &self.r
+    }
+
+    pub fn s(&self) -> &Scalar {
+        &self.s
+    }
+
+    // Whether s <= n/2. Both (r, s) and (r, n - s) verify, so Bitcoin only
+    // accepts the low form to rule out this malleability.
+    pub fn is_low_s(&self) -> bool {
+        geq_limbs(&HALF_ORDER, &self.s.value)
+    }
+
+    // Replace a high s with n - s
+    pub fn normalize_s(&mut self) {
+        if !self.is_low_s() {
+            self.s = -self.s;
+        }
+    }
+
+    // Strict DER encoding: SEQUENCE { INTEGER r, INTEGER s }
+    pub fn to_der(&self) -> Vec<u8> {
+        let r = der_integer(&self.r);
+        let s = der_integer(&self.s);
+        let mut der = Vec::with_capacity(6 + r.len() + s.len());
+        der.push(0x30);
+        der.push((4 + r.len() + s.len()) as u8);
+        for int in [r, s] {
+            der.push(0x02);
+            der.push(int.len() as u8);
+            der.extend_from_slice(&int);
+        }
+        der
+    }
+
+    // Parse a strict DER signature. Anything other than the unique minimal
+    // encoding is rejected: long-form lengths, superfluous leading zeros,
+    // negative integers, values >= n and trailing bytes.
+    pub fn from_der(der: &[u8]) -> Result<Signature, Error> {
+        // 0x30 len 0x02 rlen r 0x02 slen s, with a single-byte length
+        if der.len() < 8 || der.len() > 72 || der[0] != 0x30 || der[1] as usize != der.len() - 2 {
+            return Err(Error::InvalidEncoding);
+        }
+        let (r, rest) = parse_der_integer(&der[2..])?;
+        let (s, rest) = parse_der_integer(rest)?;
+        if !rest.is_empty() {
+            return Err(Error::InvalidEncoding);
+        }
+        // Well-formed, but zero components are outside [1, n-1]
+        if r.is_zero() || s.is_zero() {
+            return Err(Error::InvalidSignature);
+        }
+        Ok(Signature { r, s })
+    }
+
+    // Fixed-width 64-byte form r || s, each 32 bytes big-endian
+    pub fn serialize_compact(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&self.r.to_bytes());
+        bytes[32..].copy_from_slice(&self.s.to_bytes());
+        bytes
+    }
+
+    // Parse the compact form. A component >= n is ScalarOutOfRange rather
+    // than being reduced, and a zero component is InvalidSignature as in
+    // from_der.
+    pub fn from_compact(bytes: &[u8; 64]) -> Result<Signature, Error> {
+        let component = |half: &[u8]| {
+            let limbs = limbs_from_bytes(half.try_into().unwrap());
+            if geq_limbs(&limbs, &CURVE_ORDER) {
+                return Err(Error::ScalarOutOfRange);
+            }
+            Ok(Scalar::new(limbs))
+        };
+        let r = component(&bytes[..32])?;
+        let s = component(&bytes[32..])?;
+        if r.is_zero() || s.is_zero() {
+            return Err(Error::InvalidSignature);
+        }
+        Ok(Signature { r, s })
+    }
+}
+
+// Minimal big-endian two's complement content bytes of a non-negative integer
+fn der_integer(value: &Scalar) -> Vec<u8> {
+    let bytes = value.to_bytes();
+    let start = bytes.iter().position(|&b| b != 0).unwrap_or(31);
+    let mut int = Vec::with_capacity(33);
+    if bytes[start] & 0x80 != 0 {
+        int.push(0x00);
+    }
+    int.extend_from_slice(&bytes[start..]);
+    int
+}
+
+// Parse one DER INTEGER into a scalar, returning the remaining input
+fn parse_der_integer(input: &[u8]) -> Result<(Scalar, &[u8]), Error> {
+    if input.len() < 2 || input[0] != 0x02 {
+        return Err(Error::InvalidEncoding);
+    }
+    let len = input[1] as usize;
+    if len == 0 || len > 33 || input.len() < 2 + len {
+        return Err(Error::InvalidEncoding);
+    }
+    let (content, rest) = input[2..].split_at(len);
+
+    // Negative numbers are not allowed
+    if content[0] & 0x80 != 0 {
+        return Err(Error::InvalidEncoding);
+    }
+    // A leading zero is only allowed when it is needed to clear the sign bit
+    if len > 1 && content[0] == 0x00 && content[1] & 0x80 == 0 {
+        return Err(Error::InvalidEncoding);
+    }
+
+    let magnitude = if content[0] == 0x00 { &content[1..] } else { content };
+    if magnitude.len() > 32 {
+        return Err(Error::InvalidEncoding);
+    }
+    let mut bytes = [0u8; 32];
+    bytes[32 - magnitude.len()..].copy_from_slice(magnitude);
+    let limbs = limbs_from_bytes(&bytes);
+    if geq_limbs(&limbs, &CURVE_ORDER) {
+        return Err(Error::InvalidEncoding);
+    }
+    Ok((Scalar::new(limbs), rest))
+}
+
+// The x coordinate of a point, reduced modulo n
+fn x_mod_order(point: &Point) -> Option<Scalar> {
+    point.x.map(|x| Scalar::new(x.value))
+}
+
+// Sign a 32-byte message hash with the given secret key and nonce.
+// The nonce must be secret, uniformly random and never reused: two signatures
+// sharing a nonce reveal the private key.
+pub fn sign_ecdsa(privkey: &Scalar, msg_hash: &[u8; 32], nonce: &Scalar) -> Result<Signature, Error> {
+    sign_ecdsa_recoverable(privkey, msg_hash, nonce).map(|sig| sig.signature)
+}
+
+// Sign a 32-byte message hash with a nonce derived from the key and message
+// per RFC 6979, so no random number generator is needed
+#[cfg(feature = "hashes")]
+pub fn sign_ecdsa_deterministic(privkey: &Scalar, msg_hash: &[u8; 32]) -> Result<Signature, Error> {
+    sign_ecdsa(privkey, msg_hash, &rfc6979_nonce(privkey, msg_hash))
+}
+
+// A signature together with the recovery id that recover needs to find the
+// signing key. The id depends on R = k·G, which only the signer knows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RecoverableSignature {
+    signature: Signature,
+    recovery_id: u8,
+}
+
+impl RecoverableSignature {
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    // In 0..=3, with the meaning described on recover
+    pub fn recovery_id(&self) -> u8 {
+        self.recovery_id
+    }
+
+    // The public key that made this signature over msg_hash
+    pub fn recover(&self, msg_hash: &[u8; 32]) -> Option<Point> {
+        recover(msg_hash, &self.signature, self.recovery_id)
+    }
+
+    // The 65-byte form of Bitcoin signed messages: a header byte
+    // 27 + recovery_id, plus 4 if the signer's key is compressed, then the
+    // 64-byte compact r || s
+    pub fn to_compact(&self, compressed: bool) -> [u8; 65] {
+        let mut bytes = [0u8; 65];
+        bytes[0] = COMPACT_HEADER_BASE + self.recovery_id + if compressed { 4 } else { 0 };
+        bytes[1..].copy_from_slice(&self.signature.serialize_compact());
+        bytes
+    }
+
+    // Parse the to_compact form, returning the compression flag alongside.
+    // A header outside 27..=34 is InvalidEncoding; r and s are checked as
+    // in Signature::from_compact.
+    pub fn from_compact(bytes: &[u8; 65]) -> Result<(RecoverableSignature, bool), Error> {
+        let header = bytes[0]
+            .checked_sub(COMPACT_HEADER_BASE)
+            .filter(|&header| header < 8)
+            .ok_or(Error::InvalidEncoding)?;
+        let signature = Signature::from_compact(bytes[1..].try_into().unwrap())?;
+        let recoverable = RecoverableSignature {
+            signature,
+            recovery_id: header & 3,
+        };
+        Ok((recoverable, header & 4 != 0))
+    }
+}
+
+// Lowest header byte of a 65-byte recoverable signature
+const COMPACT_HEADER_BASE: u8 = 27;
+
+// sign_ecdsa, also returning the recovery id: the parity of R.y, plus 2
+// when R.x >= n so that r = R.x - n
+pub fn sign_ecdsa_recoverable(
+    privkey: &Scalar,
+    msg_hash: &[u8; 32],
+    nonce: &Scalar,
+) -> Result<RecoverableSignature, Error> {
+    if privkey.is_zero() || nonce.is_zero() {
+        return Err(Error::ZeroScalar);
+    }
+
+    // r = (k·G).x mod n
+    let big_r = Point::mul_generator(nonce);
+    let r = x_mod_order(&big_r).ok_or(Error::InvalidNonce)?;
+    if r.is_zero() {
+        return Err(Error::InvalidNonce);
+    }
+    // Both coordinates are present: big_r is not infinity
+    let overflow = big_r.x.unwrap().value != r.value;
+    let recovery_id = big_r.y.unwrap().is_odd() as u8 | (overflow as u8) << 1;
+
+    // s = k^-1 (z + r·d) mod n
+    let z = Scalar::from_bytes_reduced(msg_hash);
+    let s = nonce.inv() * (z + r * *privkey);
+    if s.is_zero() {
+        return Err(Error::InvalidNonce);
+    }
+
+    Ok(RecoverableSignature {
+        signature: Signature { r, s },
+        recovery_id,
+    })
+}
+
+// sign_ecdsa_recoverable with the RFC 6979 nonce
+#[cfg(feature = "hashes")]
+pub fn sign_ecdsa_recoverable_deterministic(
+    privkey: &Scalar,
+    msg_hash: &[u8; 32],
+) -> Result<RecoverableSignature, Error> {
+    sign_ecdsa_recoverable(privkey, msg_hash, &rfc6979_nonce(privkey, msg_hash))
+}
+
+// Verify an ECDSA signature over a 32-byte message hash
+pub fn verify_ecdsa(pubkey: &Point, msg_hash: &[u8; 32], sig: &Signature) -> bool {
+    // r and s must lie in [1, n-1]; Scalar already guarantees < n
+    if sig.r.is_zero() || sig.s.is_zero() {
+        return false;
+    }
+    if validate_public_point(pubkey).is_err() {
+        return false;
+    }
+
+    // u1 = z·s^-1, u2 = r·s^-1
+    let z = Scalar::from_bytes_reduced(msg_hash);
+    let s_inv = sig.s.inv_vartime();
+    let u1 = z * s_inv;
+    let u2 = sig.r * s_inv;
+
+    // R = u1·G + u2·Q must not be the point at infinity, and R.x mod n == r
+    let point = Point::generator().mul_add_vartime(&u1, pubkey, &u2);
+    if point.is_infinity() {
+        return false;
+    }
+    x_mod_order(&point) == Some(sig.r)
+}
+
+// Verify an ECDSA signature, additionally requiring the low-S form
+pub fn verify_ecdsa_low_s(pubkey: &Point, msg_hash: &[u8; 32], sig: &Signature) -> bool {
+    sig.is_low_s() && verify_ecdsa(pubkey, msg_hash, sig)
+}
+
+// Verify many signatures at once; true only if every one of them is valid.
+//
+// Random linear combination: with a fresh random a_i per signature,
+//   Σ a_i·u1_i·G + Σ a_i·u2_i·Q_i - Σ a_i·R_i == infinity
+// where u1_i = z_i·s_i^-1 and u2_i = r_i·s_i^-1. The G terms fold into one,
+// so the whole batch is a single multi-scalar multiply of 2n + 1 terms, and
+// all s_i are inverted together. R_i comes from r_i and the recovery id,
+// since r alone fixes R only up to sign; a signature that verify_ecdsa
+// accepts but whose recovery id is wrong fails the batch. The a_i are 128
+// bits, so an invalid batch passes with probability about 2^-128, and they
+// must be unpredictable to whoever chose the signatures: hence CryptoRng.
+pub fn verify_batch<R: CryptoRng + RngCore>(
+    items: &[(PublicKey, [u8; 32], RecoverableSignature)],
+    rng: &mut R,
+) -> bool {
+    let mut nonce_points = Vec::with_capacity(items.len());
+    for (_, _, recoverable) in items {
+        let sig = &recoverable.signature;
+        if sig.r.is_zero() || sig.s.is_zero() {
+            return false;
+        }
+        match nonce_point(sig, recoverable.recovery_id) {
+            Some(big_r) => nonce_points.push(big_r),
+            None => return false,
+        }
+    }
+
+    let mut s_invs: Vec<Scalar> = items.iter().map(|(_, _, recoverable)| recoverable.signature.s).collect();
+    Scalar::batch_inverse(&mut s_invs);
+    let mut g_coefficient = Scalar::from(0);
+    let mut pairs = Vec::with_capacity(2 * items.len() + 1);
+    for (((pubkey, msg_hash, recoverable), s_inv), big_r) in items.iter().zip(s_invs).zip(nonce_points) {
+        let a = batch_coefficient(rng);
+        let z = Scalar::from_bytes_reduced(msg_hash);
+        g_coefficient = g_coefficient + a * z * s_inv;
+        pairs.push((a * recoverable.signature.r * s_inv, *pubkey.as_point()));
+        pairs.push((-a, big_r));
+    }
+    pairs.push((g_coefficient, Point::generator()));
+    multi_scalar_mul_vartime(&pairs).is_infinity()
+}
+
+// A random non-zero 128-bit batch coefficient
+fn batch_coefficient<R: CryptoRng + RngCore>(rng: &mut R) -> Scalar {
+    loop {
+        let a = Scalar::new([rng.next_u64(), rng.next_u64(), 0, 0]);
+        if !a.is_zero() {
+            return a;
+        }
+    }
+}
+
+// R = k·G rebuilt from r and a recovery id: x is r, or r + n when bit 1 is
+// set, and bit 0 is the parity of y. None if that x is not below p or is
+// not on the curve.
+fn nonce_point(sig: &Signature, recovery_id: u8) -> Option<Point> {
+    let x = if recovery_id & 2 != 0 {
+        let (x, carry) = add_limbs(&sig.r.value, &CURVE_ORDER);
+        if carry != 0 || geq_limbs(&x, &FIELD_MODULUS) {
+            return None;
+        }
+        x
+    } else {
+        sig.r.value
+    };
+    Point::from_x(FieldElement::new(x), recovery_id & 1 == 1)
+}
+
+// Recover the public key that produced a signature. Bit 0 of the recovery id
+// is the parity of R.y; bit 1 means R.x = r + n rather than r, which is only
+// possible when r + n < p.
+pub fn recover(msg_hash: &[u8; 32], sig: &Signature, recovery_id: u8) -> Option<Point> {
+    if recovery_id > 3 || sig.r.is_zero() || sig.s.is_zero() {
+        return None;
+    }
+
+    let big_r = nonce_point(sig, recovery_id)?;
+
+    // Q = r^-1 (s·R - z·G)
+    let z = Scalar::from_bytes_reduced(msg_hash);
+    let r_inv = sig.r.inv_vartime();
+    let pubkey = big_r.mul_add_vartime(&(r_inv * sig.s), &Point::generator(), &-(r_inv * z));
+    if pubkey.is_infinity() {
+        return None;
+    }
+    Some(pubkey)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "hashes")]
+    use crate::hashes::sha256;
+    use crate::hex;
+    #[cfg(feature = "hashes")]
+    use crate::PrivateKey;
+    use crate::keys::public_key;
+    #[cfg(feature = "hashes")]
+    use crate::test_vectors::test_rng;
+    #[cfg(feature = "hashes")]
+    use rand_chacha::rand_core::RngCore;
+
+    struct Vector {
+        privkey: &'static str,
+        msg_hash: &'static str,
+        r: &'static str,
+        s: &'static str,
+    }
+
+    fn scalar(s: &str) -> Scalar {
+        Scalar::from_bytes_reduced(&hex::decode32(s))
+    }
+
+    // Signatures produced by an independent implementation (OpenSSL)
+    const VECTORS: &[Vector] = &[
+        // sha256("Satoshi Nakamoto"), d = 1
+        Vector {
+            privkey: "0000000000000000000000000000000000000000000000000000000000000001",
+            msg_hash: "a0dc65ffca799873cbea0ac274015b9526505daaaed385155425f7337704883e",
+            r: "9b3dfb1c0718308ca5ea528641d127e37227e513531a5d9cc29f24fb91f39c3e",
+            s: "7f964d2f3e5d1585885f00214e7fca22d75be124f14649e2b29b4aed0b4f56f2",
+        },
+        // sha256("sample")
+        Vector {
+            privkey: "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721",
+            msg_hash: "af2bdbe1aa9b6ec1e2ade1d694f41fc71a831d0268e9891562113d8a62add1bf",
+            r: "565dcfa44f424d60cb4c77a78e82e0d63ff1ab1454a00758a1658c912f4cc95b",
+            s: "526f5de2a83ba6822234178f2500d279366dec3785af75e2d779331c6b1494aa",
+        },
+    ];
+
+    #[test]
+    fn test_verify_known_signatures() {
+        for v in VECTORS {
+            let pubkey = public_key(&scalar(v.privkey)).unwrap();
+            let sig = Signature::new(scalar(v.r), scalar(v.s));
+            assert!(verify_ecdsa(&pubkey, &hex::decode32(v.msg_hash), &sig));
+        }
+    }
+
+    #[test]
+    fn test_sign_with_known_nonce() {
+        // d = 1, k from RFC 6979 for sha256("Satoshi Nakamoto")
+        let privkey = scalar("0000000000000000000000000000000000000000000000000000000000000001");
+        let nonce = scalar("8f8a276c19f4149656b280621e358cce24f5f52542772691ee69063b74f15d15");
+        let msg_hash = hex::decode32("a0dc65ffca799873cbea0ac274015b9526505daaaed385155425f7337704883e");
+
+        let sig = sign_ecdsa(&privkey, &msg_hash, &nonce).unwrap();
+        assert_eq!(sig.r().to_bytes(), hex::decode32("934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8"));
+        assert_eq!(sig.s().to_bytes(), hex::decode32("dbbd3162d46e9f9bef7feb87c16dc13b4f6568a87f4e83f728e2443ba586675c"));
+        assert!(verify_ecdsa(&Point::generator(), &msg_hash, &sig));
+    }
+
+    #[test]
+    #[cfg(feature = "hashes")]
+    fn test_sign_deterministic() {
+        // d = n - 1, sha256("Satoshi Nakamoto")
+        let privkey = scalar("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140");
+        let msg_hash = hex::decode32("a0dc65ffca799873cbea0ac274015b9526505daaaed385155425f7337704883e");
+
+        let sig = sign_ecdsa_deterministic(&privkey, &msg_hash).unwrap();
+        assert_eq!(sig.r().to_bytes(), hex::decode32("fd567d121db66e382991534ada77a6bd3106f0a1098c231e47993447cd6af2d0"));
+        assert_eq!(sig.s().to_bytes(), hex::decode32("94c632f14e4379fc1ea610a3df5a375152549736425ee17cebe10abbc2a2826c"));
+        assert_eq!(sign_ecdsa_deterministic(&privkey, &msg_hash), Ok(sig));
+        assert!(verify_ecdsa(&public_key(&privkey).unwrap(), &msg_hash, &sig));
+    }
+
+    #[test]
+    #[cfg(feature = "hashes")]
+    fn test_recover_fresh_signatures() {
+        for seed in 1u64..=8 {
+            let privkey = Scalar::new([seed.wrapping_mul(0x9E3779B97F4A7C15), seed, !seed, seed << 32]);
+            let pubkey = public_key(&privkey).unwrap();
+            let msg_hash = crate::hashes::sha256(&seed.to_be_bytes());
+            let sig = sign_ecdsa_deterministic(&privkey, &msg_hash).unwrap();
+
+            // The recovery id the signer would have produced
+            let big_r = Point::mul_generator(&rfc6979_nonce(&privkey, &msg_hash));
+            let overflow = big_r.x.unwrap().value != sig.r().value;
+            let id = big_r.y.unwrap().is_odd() as u8 | (overflow as u8) << 1;
+            assert_eq!(recover(&msg_hash, &sig, id), Some(pubkey));
+
+            // Every id that recovers a key yields one the signature verifies under
+            for other in 0..4 {
+                if let Some(candidate) = recover(&msg_hash, &sig, other) {
+                    assert!(verify_ecdsa(&candidate, &msg_hash, &sig));
+                    assert_eq!(candidate == pubkey, other == id);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "hashes")]
+    fn test_recoverable_signature_round_trip() {
+        let mut rng = test_rng(0x9B05_688C);
+        let mut ids_seen = [false; 2];
+        for _ in 0..64 {
+            let mut bytes = [0u8; 32];
+            rng.fill_bytes(&mut bytes);
+            let privkey = Scalar::from_bytes_reduced(&bytes);
+            rng.fill_bytes(&mut bytes);
+            let nonce = Scalar::from_bytes_reduced(&bytes);
+            let mut msg_hash = [0u8; 32];
+            rng.fill_bytes(&mut msg_hash);
+
+            let pubkey = public_key(&privkey).unwrap();
+            let recoverable = sign_ecdsa_recoverable(&privkey, &msg_hash, &nonce).unwrap();
+            assert_eq!(*recoverable.signature(), sign_ecdsa(&privkey, &msg_hash, &nonce).unwrap());
+            assert_eq!(recoverable.recover(&msg_hash), Some(pubkey));
+            assert_eq!(recover(&msg_hash, recoverable.signature(), recoverable.recovery_id()), Some(pubkey));
+            // R.x >= n has probability about 2^-128
+            assert!(recoverable.recovery_id() < 2);
+            ids_seen[recoverable.recovery_id() as usize] = true;
+        }
+        assert_eq!(ids_seen, [true, true]);
+
+        let privkey = scalar(VECTORS[0].privkey);
+        let msg_hash = hex::decode32(VECTORS[0].msg_hash);
+        let recoverable = sign_ecdsa_recoverable_deterministic(&privkey, &msg_hash).unwrap();
+        assert_eq!(*recoverable.signature(), sign_ecdsa_deterministic(&privkey, &msg_hash).unwrap());
+        assert_eq!(recoverable.recover(&msg_hash), public_key(&privkey).ok());
+    }
+
+    #[test]
+    #[cfg(feature = "hashes")]
+    fn test_recoverable_compact() {
+        // The bitcoinjs-message README example, base64 decoded: header 31
+        // means recovery id 0 and a compressed key
+        let bytes: [u8; 65] = hex::decode(
+            "1fd2f9c8b163b62d104c784fc85ad9093d55fcc32706d5ca9a888a0d9efc3430\
+             63111922e994d065d48c0ad920a0d9a9d7b072f48b49afca3b0a15f45f163dd679",
+        )
+        .unwrap()
+        .try_into()
+        .unwrap();
+        let (recoverable, compressed) = RecoverableSignature::from_compact(&bytes).unwrap();
+        assert_eq!(recoverable.recovery_id(), 0);
+        assert!(compressed);
+        assert_eq!(recoverable.to_compact(compressed), bytes);
+
+        let msg_hash = crate::hashes::bitcoin_signed_message_hash("This is an example of a signed message.");
+        let point = recoverable.recover(&msg_hash).unwrap();
+        let pubkey = PublicKey::from_slice(&point.serialize(compressed)).unwrap();
+        assert_eq!(pubkey.p2pkh_address(compressed, true), "1F3sAm6ZtwLAUnj7d38pGFxtP3RVEvtsbV");
+        assert!(verify_ecdsa(pubkey.as_point(), &msg_hash, recoverable.signature()));
+
+        // Fresh signatures round-trip with either flag
+        let privkey = scalar(VECTORS[1].privkey);
+        let msg_hash = hex::decode32(VECTORS[1].msg_hash);
+        let recoverable = sign_ecdsa_recoverable_deterministic(&privkey, &msg_hash).unwrap();
+        for compressed in [false, true] {
+            let bytes = recoverable.to_compact(compressed);
+            assert_eq!(bytes[0], 27 + recoverable.recovery_id() + 4 * compressed as u8);
+            assert_eq!(RecoverableSignature::from_compact(&bytes), Ok((recoverable, compressed)));
+        }
+
+        let mut bad = bytes;
+        for header in [0, 26, 35, 255] {
+            bad[0] = header;
+            assert_eq!(RecoverableSignature::from_compact(&bad), Err(Error::InvalidEncoding));
+        }
+        let mut zero_r = bytes;
+        zero_r[1..33].fill(0);
+        assert_eq!(RecoverableSignature::from_compact(&zero_r), Err(Error::InvalidSignature));
+    }
+
+    #[test]
+    fn test_recover_rejects_invalid_ids() {
+        let v = &VECTORS[0];
+        let msg_hash = hex::decode32(v.msg_hash);
+        let sig = Signature::new(scalar(v.r), scalar(v.s));
+        assert_eq!(recover(&msg_hash, &sig, 4), None);
+        // r is far above p - n, so R.x = r + n is impossible
+        assert_eq!(recover(&msg_hash, &sig, 2), None);
+        assert_eq!(recover(&msg_hash, &sig, 3), None);
+        let zero = Scalar::new([0, 0, 0, 0]);
+        assert_eq!(recover(&msg_hash, &Signature::new(zero, scalar(v.s)), 0), None);
+    }
+
+    #[test]
+    fn test_der_round_trip() {
+        // r has its high bit set (needs a 0x00 pad), s does not
+        let v = &VECTORS[0];
+        let sig = Signature::new(scalar(v.r), scalar(v.s));
+        let der = sig.to_der();
+        assert_eq!(
+            der,
+            hex::decode(
+                "3045\
+                 0221009b3dfb1c0718308ca5ea528641d127e37227e513531a5d9cc29f24fb91f39c3e\
+                 02207f964d2f3e5d1585885f00214e7fca22d75be124f14649e2b29b4aed0b4f56f2"
+            )
+            .unwrap()
+        );
+        assert_eq!(Signature::from_der(&der), Ok(sig));
+
+        // Short integers are encoded without padding
+        let small = Signature::new(Scalar::new([1, 0, 0, 0]), Scalar::new([0x80, 0, 0, 0]));
+        let der = small.to_der();
+        assert_eq!(der, vec![0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x02, 0x00, 0x80]);
+        assert_eq!(Signature::from_der(&der), Ok(small));
+
+        let other = Signature::new(scalar(VECTORS[1].r), scalar(VECTORS[1].s));
+        assert_eq!(Signature::from_der(&other.to_der()), Ok(other));
+    }
+
+    #[test]
+    fn test_compact_round_trip() {
+        for v in VECTORS {
+            let sig = Signature::new(scalar(v.r), scalar(v.s));
+            let compact = sig.serialize_compact();
+            assert_eq!(compact[..32], hex::decode32(v.r));
+            assert_eq!(compact[32..], hex::decode32(v.s));
+            assert_eq!(Signature::from_compact(&compact), Ok(sig));
+        }
+
+        let small = Signature::new(Scalar::new([1, 0, 0, 0]), Scalar::new([0x80, 0, 0, 0]));
+        let compact = small.serialize_compact();
+        assert_eq!(compact[31], 0x01);
+        assert_eq!(compact[63], 0x80);
+        assert_eq!(Signature::from_compact(&compact), Ok(small));
+    }
+
+    #[test]
+    fn test_compact_rejects_out_of_range() {
+        let valid = Signature::new(scalar(VECTORS[0].r), scalar(VECTORS[0].s)).serialize_compact();
+        let order = hex::decode32("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+
+        // r = n, s = n and s = 2^256 - 1
+        let mut bytes = valid;
+        bytes[..32].copy_from_slice(&order);
+        assert_eq!(Signature::from_compact(&bytes), Err(Error::ScalarOutOfRange));
+        let mut bytes = valid;
+        bytes[32..].copy_from_slice(&order);
+        assert_eq!(Signature::from_compact(&bytes), Err(Error::ScalarOutOfRange));
+        let mut bytes = valid;
+        bytes[32..].copy_from_slice(&[0xFF; 32]);
+        assert_eq!(Signature::from_compact(&bytes), Err(Error::ScalarOutOfRange));
+
+        // n - 1 is the largest accepted value; zero is not a valid component
+        let mut bytes = valid;
+        bytes[..32].copy_from_slice(&order);
+        bytes[31] -= 1;
+        assert!(Signature::from_compact(&bytes).is_ok());
+        let mut bytes = valid;
+        bytes[..32].copy_from_slice(&[0; 32]);
+        assert_eq!(Signature::from_compact(&bytes), Err(Error::InvalidSignature));
+    }
+
+    #[test]
+    fn test_der_rejects_malformed() {
+        let reject = |bytes: &[u8]| assert_eq!(Signature::from_der(bytes), Err(Error::InvalidEncoding), "{:02x?}", bytes);
+
+        // Extra zero padding on r
+        reject(&[0x30, 0x08, 0x02, 0x02, 0x00, 0x01, 0x02, 0x02, 0x00, 0x80]);
+        // Wrong sequence tag and wrong integer tag
+        reject(&[0x31, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01]);
+        reject(&[0x30, 0x06, 0x03, 0x01, 0x01, 0x02, 0x01, 0x01]);
+        // Trailing bytes, both outside and inside the sequence
+        reject(&[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01, 0x00]);
+        reject(&[0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01, 0x00]);
+        // Negative s
+        reject(&[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x81]);
+        // Zero-length integer
+        reject(&[0x30, 0x06, 0x02, 0x00, 0x02, 0x02, 0x01, 0x01]);
+        // Integer length running past the end
+        reject(&[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x05, 0x01]);
+        // Long-form sequence length
+        reject(&[0x30, 0x81, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01]);
+        // Truncated
+        reject(&[0x30, 0x06, 0x02, 0x01, 0x01, 0x02]);
+        reject(&[]);
+
+        // r = n does not fit in a scalar
+        let mut overflow = vec![0x30, 0x25, 0x02, 0x21, 0x00];
+        overflow.extend_from_slice(&hex::decode32("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"));
+        overflow.extend_from_slice(&[0x02, 0x01, 0x01]);
+        reject(&overflow);
+
+        // Well-formed DER with a zero component is not a usable signature
+        assert_eq!(
+            Signature::from_der(&[0x30, 0x06, 0x02, 0x01, 0x00, 0x02, 0x01, 0x01]),
+            Err(Error::InvalidSignature)
+        );
+        assert_eq!(
+            Signature::from_der(&[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x00]),
+            Err(Error::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_half_order() {
+        // 2·(n/2) + 1 == n
+        let half = Scalar { value: HALF_ORDER };
+        assert!((half + half + Scalar::new([1, 0, 0, 0])).is_zero());
+        assert!(Signature::new(half, half).is_low_s());
+        assert!(!Signature::new(half, half + Scalar::new([1, 0, 0, 0])).is_low_s());
+    }
+
+    #[test]
+    #[cfg(feature = "hashes")]
+    fn test_normalize_high_s() {
+        // The RFC 6979 signature for d = 1 and "Satoshi Nakamoto" has a high s
+        let privkey = scalar("0000000000000000000000000000000000000000000000000000000000000001");
+        let msg_hash = hex::decode32("a0dc65ffca799873cbea0ac274015b9526505daaaed385155425f7337704883e");
+        let mut sig = sign_ecdsa_deterministic(&privkey, &msg_hash).unwrap();
+        let pubkey = Point::generator();
+        assert!(!sig.is_low_s());
+        assert!(verify_ecdsa(&pubkey, &msg_hash, &sig));
+        assert!(!verify_ecdsa_low_s(&pubkey, &msg_hash, &sig));
+
+        sig.normalize_s();
+        assert!(sig.is_low_s());
+        assert_eq!(sig.s().to_bytes(), hex::decode32("2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5"));
+        assert!(verify_ecdsa(&pubkey, &msg_hash, &sig));
+        assert!(verify_ecdsa_low_s(&pubkey, &msg_hash, &sig));
+
+        // Normalizing a low-S signature is a no-op
+        let before = sig;
+        sig.normalize_s();
+        assert_eq!(sig, before);
+    }
+
+    #[test]
+    fn test_verify_rejects_tampering() {
+        let v = &VECTORS[1];
+        let pubkey = public_key(&scalar(v.privkey)).unwrap();
+        let msg_hash = hex::decode32(v.msg_hash);
+        let sig = Signature::new(scalar(v.r), scalar(v.s));
+        assert!(verify_ecdsa(&pubkey, &msg_hash, &sig));
+
+        // Tampered message
+        let mut tampered = msg_hash;
+        tampered[31] ^= 1;
+        assert!(!verify_ecdsa(&pubkey, &tampered, &sig));
+
+        // Flipped bit in s
+        let mut s_bytes = sig.s().to_bytes();
+        s_bytes[0] ^= 0x10;
+        let flipped = Signature::new(*sig.r(), Scalar::from_bytes_reduced(&s_bytes));
+        assert!(!verify_ecdsa(&pubkey, &msg_hash, &flipped));
+
+        // Signature checked against a different key
+        assert!(!verify_ecdsa(&Point::generator(), &msg_hash, &sig));
+    }
+
+    #[test]
+    fn test_verify_rejects_out_of_range() {
+        let v = &VECTORS[0];
+        let msg_hash = hex::decode32(v.msg_hash);
+        let zero = Scalar::new([0, 0, 0, 0]);
+        let generator = Point::generator();
+
+        assert!(!verify_ecdsa(&generator, &msg_hash, &Signature::new(zero, scalar(v.s))));
+        assert!(!verify_ecdsa(&generator, &msg_hash, &Signature::new(scalar(v.r), zero)));
+        // The point at infinity is never a valid public key
+        let sig = Signature::new(scalar(v.r), scalar(v.s));
+        assert!(!verify_ecdsa(&Point::infinity(), &msg_hash, &sig));
+        // Nor is a point off the curve
+        let off_curve = Point { x: generator.x, y: Some(generator.y.unwrap() + FieldElement::from(1)) };
+        assert!(!verify_ecdsa(&off_curve, &msg_hash, &sig));
+    }
+
+    #[test]
+    fn test_sign_rejects_zero_inputs() {
+        let msg_hash = [0u8; 32];
+        let zero = Scalar::new([0, 0, 0, 0]);
+        let one = Scalar::new([1, 0, 0, 0]);
+        assert_eq!(sign_ecdsa(&zero, &msg_hash, &one), Err(Error::ZeroScalar));
+        assert_eq!(sign_ecdsa(&one, &msg_hash, &zero), Err(Error::ZeroScalar));
+    }
+
+    #[cfg(feature = "hashes")]
+    fn signed_batch(count: u64) -> Vec<(PublicKey, [u8; 32], RecoverableSignature)> {
+        (1..=count)
+            .map(|i| {
+                let mut secret = [0u8; 32];
+                secret[..8].copy_from_slice(&i.to_be_bytes());
+                secret[31] = 0x5a;
+                let privkey = PrivateKey::from_bytes(&secret).unwrap();
+                let msg_hash = sha256(&i.to_le_bytes());
+                let sig = sign_ecdsa_recoverable_deterministic(privkey.as_scalar(), &msg_hash).unwrap();
+                (privkey.public_key(), msg_hash, sig)
+            })
+            .collect()
+    }
+
+    #[test]
+    #[cfg(feature = "hashes")]
+    fn test_verify_batch() {
+        let mut rng = test_rng(0x3C6E_F372);
+        let mut batch = signed_batch(8);
+        assert!(verify_batch(&batch, &mut rng));
+        assert!(verify_batch(&batch[..1], &mut rng));
+        assert!(verify_batch(&[], &mut rng));
+
+        // One bad signature anywhere fails the whole batch
+        for i in [0, 5, 7] {
+            let original = batch[i].2;
+            let sig = original.signature;
+            batch[i].2.signature = Signature::new(sig.r, sig.s + Scalar::new([1, 0, 0, 0]));
+            assert!(!verify_batch(&batch, &mut rng));
+            // So does a valid signature with the wrong R
+            batch[i].2.recovery_id ^= 1;
+            batch[i].2.signature = sig;
+            assert!(verify_ecdsa(batch[i].0.as_point(), &batch[i].1, &sig));
+            assert!(!verify_batch(&batch, &mut rng));
+            batch[i].2 = original;
+        }
+
+        // A signature moved onto another message, or a zero component
+        batch[3].1 = batch[4].1;
+        assert!(!verify_batch(&batch, &mut rng));
+        let mut batch = signed_batch(3);
+        batch[1].2.signature = Signature::new(batch[1].2.signature.r, Scalar::new([0, 0, 0, 0]));
+        assert!(!verify_batch(&batch, &mut rng));
+
+        // Past 32 terms the multi-scalar multiply switches to Pippenger
+        let mut batch = signed_batch(20);
+        assert!(verify_batch(&batch, &mut rng));
+        batch[13].1[0] ^= 1;
+        assert!(!verify_batch(&batch, &mut rng));
+    }
+
+    #[test]
+    #[cfg(feature = "hashes")]
+    fn test_verify_batch_any_rng() {
+        use rand_chacha::rand_core::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        // The coefficients change with the generator but the verdict does not
+        let mut batch = signed_batch(4);
+        let mut chacha20 = ChaCha20Rng::from_seed([7u8; 32]);
+        assert!(verify_batch(&batch, &mut chacha20));
+        for seed in [1, 0x6A09_E667, u64::MAX] {
+            assert!(verify_batch(&batch, &mut test_rng(seed)));
+        }
+        batch[2].1 = batch[0].1;
+        assert!(!verify_batch(&batch, &mut chacha20));
+        for seed in [1, 0x6A09_E667, u64::MAX] {
+            assert!(!verify_batch(&batch, &mut test_rng(seed)));
+        }
+    }
+}
@@ -0,0 +1,34 @@
+// Fuzz entry points, built with RUSTFLAGS="--cfg fuzz". Each takes the raw
+// fuzzer input and panics only if an invariant is broken, so a fuzz target
+// is just a call to one of them.
+
+use crate::{Point, PublicKey};
+
+// Point::from_sec1 and PublicKey::from_slice must never panic and must
+// agree, and whatever they accept must be a finite point on the curve that
+// re-encodes to the input
+pub fn point_from_slice(data: &[u8]) {
+    let point = Point::from_sec1(data);
+    let pubkey = PublicKey::from_slice(data);
+    assert_eq!(pubkey.map(|key| *key.as_point()), point);
+    if let Ok(point) = point {
+        assert!(!point.is_infinity());
+        assert!(point.is_on_curve());
+        let compressed = data.len() == 33;
+        assert_eq!(point.serialize(compressed), data);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point_from_slice_edge_cases() {
+        for data in [&[][..], &[0x00], &[0x02], &[0x04], &[0xFF; 33], &[0xFF; 65], &[0x04; 66]] {
+            point_from_slice(data);
+            assert!(Point::from_sec1(data).is_err(), "{:02x?}", data);
+            assert!(PublicKey::from_slice(data).is_err(), "{:02x?}", data);
+        }
+    }
+}
@@ -0,0 +1,203 @@
+// GLV endomorphism: φ(x, y) = (β·x, y) equals λ·(x, y) for every point, so
+// k·P = k1·P + k2·φ(P) with k = k1 + k2·λ mod n. With a good split both
+// halves are about 128 bits, halving the number of doublings.
+
+use crate::msm::interleaved_wnaf;
+use crate::{add_limbs, geq_limbs, mul_wide, FieldElement, JacobianPoint, Point, Scalar};
+
+// β, a primitive cube root of unity mod p
+const BETA: [u64; 4] = [
+    0xC1396C28719501EE,
+    0x9CF0497512F58995,
+    0x6E64479EAC3434E9,
+    0x7AE96A2B657C0710,
+];
+
+// λ, the matching cube root of unity mod n
+const LAMBDA: [u64; 4] = [
+    0xDF02967C1B23BD72,
+    0x122E22EA20816678,
+    0xA5261C028812645A,
+    0x5363AD4CC05C30E0,
+];
+
+// Lattice basis for the split, as used by libsecp256k1: -b1 and -b2 mod n,
+// and g1, g2 = round(2^384·b2/n), round(2^384·(-b1)/n)
+const MINUS_B1: [u64; 4] = [0x6F547FA90ABFE4C3, 0xE4437ED6010E8828, 0, 0];
+const MINUS_B2: [u64; 4] = [
+    0xD765CDA83DB1562C,
+    0x8A280AC50774346D,
+    0xFFFFFFFFFFFFFFFE,
+    0xFFFFFFFFFFFFFFFF,
+];
+const G1: [u64; 4] = [
+    0xE893209A45DBB031,
+    0x3DAA8A1471E8CA7F,
+    0xE86C90E49284EB15,
+    0x3086D221A7D46BCD,
+];
+const G2: [u64; 4] = [
+    0x1571B4AE8AC47F71,
+    0x221208AC9DF506C6,
+    0x6F547FA90ABFE4C4,
+    0xE4437ED6010E8828,
+];
+
+// round(k·g / 2^384)
+fn mul_shift_384(k: &Scalar, g: &[u64; 4]) -> Scalar {
+    let wide = mul_wide(&k.value, g);
+    let mut high = [wide[6], wide[7], 0, 0];
+    // Round by adding bit 383
+    if wide[5] >> 63 == 1 {
+        high = add_limbs(&high, &[1, 0, 0, 0]).0;
+    }
+    Scalar::new(high)
+}
+
+// Split k into (k1, k2) with k = k1 + k2·λ mod n. Either half may come out
+// as the negation of a short value, which the caller folds into the point.
+fn split(k: &Scalar) -> (Scalar, Scalar) {
+    let c1 = mul_shift_384(k, &G1);
+    let c2 = mul_shift_384(k, &G2);
+    let k2 = c1 * Scalar::new(MINUS_B1) + c2 * Scalar::new(MINUS_B2);
+    let k1 = *k - k2 * Scalar::new(LAMBDA);
+    (k1, k2)
+}
+
+// Return (|k|, P or -P) such that |k|·(±P) = k·P and |k| < n/2
+fn shorten(k: Scalar, point: Point) -> (Scalar, Point) {
+    let negated = -k;
+    if !geq_limbs(&negated.value, &k.value) {
+        let flipped = Point {
+            x: point.x,
+            y: point.y.map(|y| -y),
+        };
+        (negated, flipped)
+    } else {
+        (k, point)
+    }
+}
+
+// k·P as two terms k1·P + k2·φ(P) with k1 and k2 below 2^128, ready for
+// the interleaved wNAF of msm
+pub(crate) fn decompose(k: &Scalar, point: &Point) -> [(Scalar, Point); 2] {
+    let endo = Point {
+        x: point.x.map(|x| x * FieldElement::new(BETA)),
+        y: point.y,
+    };
+    let (k1, k2) = split(k);
+    let (k1, p) = shorten(k1, *point);
+    let (k2, q) = shorten(k2, endo);
+    debug_assert!(k1.value[2..] == [0, 0] && k2.value[2..] == [0, 0]);
+    [(k1, p), (k2, q)]
+}
+
+impl Point {
+    // k·P for public k. The endomorphism splits k into two ~128-bit halves,
+    // which msm's interleaved width-5 wNAF multiplies over one chain of about
+    // 128 doublings. Variable time: the digits decide which additions run
+    // and which table entries are read, so never pass it a secret; use
+    // scalar_mul for those.
+    pub fn scalar_mul_vartime(&self, k: &Scalar) -> Point {
+        interleaved_wnaf(&decompose(k, self)).to_affine()
+    }
+
+    // a·self + b·other for public scalars, as verification needs: both are
+    // split, and the four ~128-bit halves share one chain of doublings. Does
+    // about half the doublings of a plain Shamir's trick.
+    pub fn mul_add_vartime(&self, a: &Scalar, other: &Point, b: &Scalar) -> Point {
+        self.mul_add_jacobian(a, other, b).to_affine()
+    }
+
+    // mul_add_vartime without the final conversion to affine
+    pub(crate) fn mul_add_jacobian(&self, a: &Scalar, other: &Point, b: &Scalar) -> JacobianPoint {
+        let [first, second] = decompose(a, self);
+        let [third, fourth] = decompose(b, other);
+        interleaved_wnaf(&[first, second, third, fourth])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_vectors::test_rng;
+    use rand_chacha::rand_core::RngCore;
+
+    #[test]
+    fn test_endomorphism_constants() {
+        // φ(G) == λ·G, and β³ == 1, λ³ == 1
+        let generator = Point::generator();
+        let endo = Point {
+            x: Some(generator.x.unwrap() * FieldElement::new(BETA)),
+            y: generator.y,
+        };
+        assert_eq!(generator.scalar_mul(&LAMBDA), endo);
+
+        let beta = FieldElement::new(BETA);
+        assert_eq!(beta * beta * beta, FieldElement::from(1));
+        let lambda = Scalar::new(LAMBDA);
+        assert_eq!(lambda * lambda * lambda, Scalar::new([1, 0, 0, 0]));
+    }
+
+    #[test]
+    fn test_split_is_short() {
+        let mut rng = test_rng(0x9E37_79B9);
+        for _ in 0..200 {
+            let k = Scalar::new([rng.next_u64(), rng.next_u64(), rng.next_u64(), rng.next_u64()]);
+            let (k1, k2) = split(&k);
+            assert_eq!(k1 + k2 * Scalar::new(LAMBDA), k);
+            let (short1, _) = shorten(k1, Point::generator());
+            let (short2, _) = shorten(k2, Point::generator());
+            assert_eq!(short1.value[2..], [0, 0]);
+            assert_eq!(short2.value[2..], [0, 0]);
+        }
+    }
+
+    #[test]
+    fn test_mul_add_vartime_matches_scalar_mul() {
+        let g = Point::generator();
+        let q = g.scalar_mul(&[0xABCDEF, 0, 0, 0x1234]);
+        let a = Scalar::new([0x0123456789ABCDEF, 0xFEDCBA9876543210, 0xDEADBEEFCAFEBABE, 0x7FFFFFFFFFFFFFFF]);
+        let b = -Scalar::new(LAMBDA);
+        for (a, b) in [(a, b), (b, a), (Scalar::from(0), b), (a, Scalar::from(0))] {
+            let expected = g.scalar_mul(&a.value).add(&q.scalar_mul(&b.value));
+            assert_eq!(g.mul_add_vartime(&a, &q, &b), expected);
+        }
+        // Terms that cancel, and a point at infinity
+        assert!(g.mul_add_vartime(&a, &g, &-a).is_infinity());
+        assert_eq!(g.mul_add_vartime(&a, &Point::infinity(), &b), g.scalar_mul(&a.value));
+    }
+
+    #[test]
+    fn test_scalar_mul_vartime_matches_scalar_mul() {
+        let points = [
+            Point::generator(),
+            Point::generator().scalar_mul(&[0xABCDEF, 0, 0, 0x1234]),
+        ];
+        let mut scalars = vec![
+            Scalar::new([0, 0, 0, 0]),
+            Scalar::new([1, 0, 0, 0]),
+            -Scalar::new([1, 0, 0, 0]),
+            Scalar::new(LAMBDA),
+            Scalar::new(MINUS_B1),
+        ];
+        let mut rng = test_rng(0x7F4A_7C15);
+        for _ in 0..32 {
+            scalars.push(Scalar::new([rng.next_u64(), rng.next_u64(), rng.next_u64(), rng.next_u64()]));
+        }
+
+        for point in points {
+            for k in &scalars {
+                assert_eq!(
+                    point.scalar_mul_vartime(k),
+                    point.scalar_mul(&k.value),
+                    "k = {:?}",
+                    k
+                );
+            }
+        }
+        assert!(Point::infinity()
+            .scalar_mul_vartime(&Scalar::new([3, 0, 0, 0]))
+            .is_infinity());
+    }
+}
@@ -0,0 +1,376 @@
+use crate::hashes::Sha256;
+use crate::{limbs_from_bytes, reduce_field_wide, FieldElement, Point};
+
+// Hashing to the curve per RFC 9380, suite secp256k1_XMD:SHA-256_SSWU_RO_.
+// The simplified SWU map needs a curve with a, b both nonzero, so it maps to
+// the 3-isogenous curve E': y² = x³ + A'·x + B' and the isogeny carries the
+// result back to secp256k1 (appendix E.1 and section 8.7 of the RFC).
+
+// E' coefficients and the SSWU constant Z = -11
+const ISO_A: [u64; 4] = [
+    0x405447C01A444533,
+    0xE953D363CB6F0E5D,
+    0xA08A5558F0F5D272,
+    0x3F8731ABDD661ADC,
+];
+const ISO_B: [u64; 4] = [1771, 0, 0, 0];
+const SSWU_Z: [u64; 4] = [
+    0xFFFFFFFEFFFFFC24,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+];
+
+// Isogeny map polynomials, constant term first. The denominators are monic,
+// so their leading 1 is left implicit.
+const X_NUM: [[u64; 4]; 4] = [
+    [
+        0x8E38E38DAAAAA8C7,
+        0x38E38E38E38E38E3,
+        0xE38E38E38E38E38E,
+        0x8E38E38E38E38E38,
+    ],
+    [
+        0xDFFF1044F17C6581,
+        0xD595D2FC0BF63B92,
+        0xB9F315CEA7FD44C5,
+        0x07D3D4C80BC321D5,
+    ],
+    [
+        0x4ECBD0B53D9DD262,
+        0xE4506144037C4031,
+        0xE2A413DECA25CAEC,
+        0x534C328D23F234E6,
+    ],
+    [
+        0x8E38E38DAAAAA88C,
+        0x38E38E38E38E38E3,
+        0xE38E38E38E38E38E,
+        0x8E38E38E38E38E38,
+    ],
+];
+const X_DEN: [[u64; 4]; 2] = [
+    [
+        0x9FE6B745781EB49B,
+        0x86CD409542F8487D,
+        0x9CA34CCBB7B640DD,
+        0xD35771193D94918A,
+    ],
+    [
+        0xC52A56612A8C6D14,
+        0x06D36B641F5E41BB,
+        0xF7C4B2D51B542254,
+        0xEDADC6F64383DC1D,
+    ],
+];
+const Y_NUM: [[u64; 4]; 4] = [
+    [
+        0xA12F684B8E38E23C,
+        0x2F684BDA12F684BD,
+        0x684BDA12F684BDA1,
+        0x4BDA12F684BDA12F,
+    ],
+    [
+        0xDFFC90FC201D71A3,
+        0x647AB046D686DA6F,
+        0xA9D0A54B12A0A6D5,
+        0xC75E0C32D5CB7C0F,
+    ],
+    [
+        0xA765E85A9ECEE931,
+        0x722830A201BE2018,
+        0x715209EF6512E576,
+        0x29A6194691F91A73,
+    ],
+    [
+        0x84BDA12F38E38D84,
+        0xBDA12F684BDA12F6,
+        0xA12F684BDA12F684,
+        0x2F684BDA12F684BD,
+    ],
+];
+const Y_DEN: [[u64; 4]; 3] = [
+    [
+        0xFFFFFFFEFFFFF93B,
+        0xFFFFFFFFFFFFFFFF,
+        0xFFFFFFFFFFFFFFFF,
+        0xFFFFFFFFFFFFFFFF,
+    ],
+    [
+        0xDFB425D2685C2573,
+        0x9467C1BFC8E8D978,
+        0xD5E9E6632722C298,
+        0x7A06534BB8BDB49F,
+    ],
+    [
+        0xA7BF8192BFD2A76F,
+        0x0A3D21162F0D6299,
+        0xF3A70C3FA8FE337E,
+        0x6484AA716545CA2C,
+    ],
+];
+
+// Two field elements of L = 48 bytes each (256 bits plus 128 bits of
+// headroom so the reduction mod p is unbiased)
+const ELEMENT_BYTES: usize = 48;
+const UNIFORM_BYTES: usize = 2 * ELEMENT_BYTES;
+
+// expand_message_xmd with SHA-256 (RFC 9380 section 5.3.1), fixed to the
+// output length hash_to_field needs
+fn expand_message_xmd(msg: &[u8], dst: &[u8]) -> [u8; UNIFORM_BYTES] {
+    // Tags longer than 255 bytes are replaced by a hash of themselves
+    let hashed_dst;
+    let dst = if dst.len() > 255 {
+        let mut hasher = Sha256::new();
+        hasher.update(b"H2C-OVERSIZE-DST-");
+        hasher.update(dst);
+        hashed_dst = hasher.finalize();
+        &hashed_dst[..]
+    } else {
+        dst
+    };
+    let dst_len = [dst.len() as u8];
+
+    // b_0 = H(Z_pad || msg || l_i_b_str || 0 || DST_prime)
+    let mut hasher = Sha256::new();
+    hasher.update(&[0u8; 64]);
+    hasher.update(msg);
+    hasher.update(&(UNIFORM_BYTES as u16).to_be_bytes());
+    hasher.update(&[0]);
+    hasher.update(dst);
+    hasher.update(&dst_len);
+    let b0 = hasher.finalize();
+
+    // b_i = H((b_0 xor b_(i-1)) || i || DST_prime), with b_1 = H(b_0 || 1 || DST_prime)
+    let mut output = [0u8; UNIFORM_BYTES];
+    let mut previous = [0u8; 32];
+    for (i, chunk) in output.chunks_exact_mut(32).enumerate() {
+        let mut block = b0;
+        for (byte, prev) in block.iter_mut().zip(previous.iter()) {
+            *byte ^= prev;
+        }
+        let mut hasher = Sha256::new();
+        hasher.update(&block);
+        hasher.update(&[i as u8 + 1]);
+        hasher.update(dst);
+        hasher.update(&dst_len);
+        previous = hasher.finalize();
+        chunk.copy_from_slice(&previous);
+    }
+    output
+}
+
+// A 48-byte big-endian integer reduced mod p
+fn field_from_wide_bytes(bytes: &[u8]) -> FieldElement {
+    let mut padded = [0u8; 64];
+    padded[64 - bytes.len()..].copy_from_slice(bytes);
+    let hi = limbs_from_bytes(padded[..32].try_into().unwrap());
+    let lo = limbs_from_bytes(padded[32..].try_into().unwrap());
+    let wide = [lo[0], lo[1], lo[2], lo[3], hi[0], hi[1], hi[2], hi[3]];
+    FieldElement::new(reduce_field_wide(wide))
+}
+
+// hash_to_field(msg, 2) from RFC 9380 section 5.2
+fn hash_to_field(msg: &[u8], dst: &[u8]) -> [FieldElement; 2] {
+    let uniform = expand_message_xmd(msg, dst);
+    let (first, second) = uniform.split_at(ELEMENT_BYTES);
+    [field_from_wide_bytes(first), field_from_wide_bytes(second)]
+}
+
+// Simplified SWU map onto E' (RFC 9380 section 6.6.2), returning (x, y)
+fn map_to_isogenous_curve(u: FieldElement) -> (FieldElement, FieldElement) {
+    let a = FieldElement::new(ISO_A);
+    let b = FieldElement::new(ISO_B);
+    let z = FieldElement::new(SSWU_Z);
+    let curve = |x: FieldElement| x * x * x + a * x + b;
+
+    let zu2 = z * u * u;
+    // inv0: the inverse, or zero for zero
+    let tv1 = (zu2 * zu2 + zu2).inv();
+    let x1 = if tv1.is_zero() {
+        b * (z * a).inv()
+    } else {
+        -b * a.inv() * (FieldElement::from(1) + tv1)
+    };
+
+    // Exactly one of g(x1) and g(x2) = Z·u²·g(x1)·(...)³ is a square
+    let (x, y) = match curve(x1).sqrt() {
+        Some(y) => (x1, y),
+        None => {
+            let x2 = zu2 * x1;
+            let y = curve(x2).sqrt().expect("g(x2) is square when g(x1) is not");
+            (x2, y)
+        }
+    };
+
+    // sgn0 for a prime field is the parity
+    let y = if y.is_odd() == u.is_odd() { y } else { -y };
+    (x, y)
+}
+
+// Horner evaluation; a monic polynomial gets the implicit leading 1
+fn evaluate(coefficients: &[[u64; 4]], x: FieldElement, monic: bool) -> FieldElement {
+    let mut result = if monic {
+        FieldElement::from(1)
+    } else {
+        FieldElement::from(0)
+    };
+    for coefficient in coefficients.iter().rev() {
+        result = result * x + FieldElement::new(*coefficient);
+    }
+    result
+}
+
+// The 3-isogeny from E' to secp256k1 (RFC 9380 appendix E.1)
+fn isogeny_map(x: FieldElement, y: FieldElement) -> Point {
+    let x_num = evaluate(&X_NUM, x, false);
+    let x_den = evaluate(&X_DEN, x, true);
+    let y_num = evaluate(&Y_NUM, x, false);
+    let y_den = evaluate(&Y_DEN, x, true);
+    // The denominators vanish only on the isogeny's kernel, which maps to
+    // the identity
+    if x_den.is_zero() || y_den.is_zero() {
+        return Point::infinity();
+    }
+    Point {
+        x: Some(x_num * x_den.inv()),
+        y: Some(y * y_num * y_den.inv()),
+    }
+}
+
+fn map_to_curve(u: FieldElement) -> Point {
+    let (x, y) = map_to_isogenous_curve(u);
+    isogeny_map(x, y)
+}
+
+// Hash arbitrary bytes to a curve point with the RFC 9380 random-oracle
+// suite secp256k1_XMD:SHA-256_SSWU_RO_. dst is the domain separation tag
+// and should be unique to the application. The result is the sum of two
+// independently mapped points; it is on the curve, and it is infinity only
+// if the two collide as negations, which no one can arrange without breaking
+// SHA-256. The cofactor is 1, so no clearing is needed.
+pub fn hash_to_curve(msg: &[u8], dst: &[u8]) -> Point {
+    let [u0, u1] = hash_to_field(msg, dst);
+    map_to_curve(u0).add(&map_to_curve(u1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    const DST: &[u8] = b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_";
+
+    fn decode(s: &str) -> FieldElement {
+        FieldElement::from(hex::decode32(s))
+    }
+
+    #[test]
+    fn test_hash_to_curve_vectors() {
+        // RFC 9380 appendix J.8.1: (msg, P.x, P.y)
+        let mut a512 = b"a512_".to_vec();
+        a512.extend_from_slice(&[b'a'; 512]);
+        let vectors: [(&[u8], &str, &str); 5] = [
+            (
+                b"",
+                "c1cae290e291aee617ebaef1be6d73861479c48b841eaba9b7b5852ddfeb1346",
+                "64fa678e07ae116126f08b022a94af6de15985c996c3a91b64c406a960e51067",
+            ),
+            (
+                b"abc",
+                "3377e01eab42db296b512293120c6cee72b6ecf9f9205760bd9ff11fb3cb2c4b",
+                "7f95890f33efebd1044d382a01b1bee0900fb6116f94688d487c6c7b9c8371f6",
+            ),
+            (
+                b"abcdef0123456789",
+                "bac54083f293f1fe08e4a70137260aa90783a5cb84d3f35848b324d0674b0e3a",
+                "4436476085d4c3c4508b60fcf4389c40176adce756b398bdee27bca19758d828",
+            ),
+            (
+                b"q128_qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq",
+                "e2167bc785333a37aa562f021f1e881defb853839babf52a7f72b102e41890e9",
+                "f2401dd95cc35867ffed4f367cd564763719fbc6a53e969fb8496a1e6685d873",
+            ),
+            (
+                &a512,
+                "e3c8d35aaaf0b9b647e88a0a0a7ee5d5bed5ad38238152e4e6fd8c1f8cb7c998",
+                "8446eeb6181bf12f56a9d24e262221cc2f0c4725c7e3803024b5888ee5823aa6",
+            ),
+        ];
+
+        for (msg, x, y) in vectors {
+            let point = hash_to_curve(msg, DST);
+            assert_eq!(
+                point,
+                Point {
+                    x: Some(decode(x)),
+                    y: Some(decode(y))
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_hash_to_curve_steps() {
+        // Intermediate values for msg = "abc" from the same appendix
+        let [u0, u1] = hash_to_field(b"abc", DST);
+        assert_eq!(
+            u0,
+            decode("128aab5d3679a1f7601e3bdf94ced1f43e491f544767e18a4873f397b08a2b61")
+        );
+        assert_eq!(
+            u1,
+            decode("5897b65da3b595a813d0fdcc75c895dc531be76a03518b044daaa0f2e4689e00")
+        );
+
+        let q0 = map_to_curve(u0);
+        assert_eq!(
+            q0.x,
+            Some(decode(
+                "07dd9432d426845fb19857d1b3a91722436604ccbbbadad8523b8fc38a5322d7"
+            ))
+        );
+        assert_eq!(
+            q0.y,
+            Some(decode(
+                "604588ef5138cffe3277bbd590b8550bcbe0e523bbaf1bed4014a467122eb33f"
+            ))
+        );
+        let q1 = map_to_curve(u1);
+        assert_eq!(
+            q1.x,
+            Some(decode(
+                "e9ef9794d15d4e77dde751e06c182782046b8dac05f8491eb88764fc65321f78"
+            ))
+        );
+        assert_eq!(
+            q1.y,
+            Some(decode(
+                "cb07ce53670d5314bf236ee2c871455c562dd76314aa41f012919fe8e7f717b3"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_hash_to_curve_deterministic_and_on_curve() {
+        for i in 0u32..32 {
+            let msg = i.to_be_bytes();
+            let point = hash_to_curve(&msg, b"test-dst");
+            assert!(!point.is_infinity());
+            assert!(point.is_on_curve());
+            assert_eq!(point, hash_to_curve(&msg, b"test-dst"));
+            assert_ne!(point, hash_to_curve(&msg, b"other-dst"));
+        }
+
+        // An oversized tag is hashed down rather than rejected
+        let long_dst = [b'd'; 300];
+        assert!(hash_to_curve(b"msg", &long_dst).is_on_curve());
+        let mut hasher = Sha256::new();
+        hasher.update(b"H2C-OVERSIZE-DST-");
+        hasher.update(&long_dst);
+        assert_eq!(
+            hash_to_curve(b"msg", &long_dst),
+            hash_to_curve(b"msg", &hasher.finalize())
+        );
+    }
+}
@@ -0,0 +1,404 @@
+// SHA-256 (FIPS 180-4), HMAC-SHA256 (RFC 2104) and RIPEMD-160 over a
+// pluggable backend. The compression functions come from the sha2 and
+// ripemd crates, which the hashes feature pulls in; Sha256, HmacSha256 and
+// Ripemd160 take the backend as a type parameter defaulting to them, so
+// another implementation (a hardware engine, say) plugs in by implementing
+// Sha256Backend or Ripemd160Backend. Only the constructions on top, such as
+// HMAC, sha256d, HASH160 and tagged hashes, are written here.
+
+use sha2::Digest;
+
+// An incremental SHA-256 implementation
+pub trait Sha256Backend: Clone + Default {
+    fn update(&mut self, data: &[u8]);
+    fn finalize(self) -> [u8; 32];
+}
+
+impl Sha256Backend for sha2::Sha256 {
+    fn update(&mut self, data: &[u8]) {
+        Digest::update(self, data);
+    }
+
+    fn finalize(self) -> [u8; 32] {
+        Digest::finalize(self).into()
+    }
+}
+
+// An incremental RIPEMD-160 implementation
+pub trait Ripemd160Backend: Clone + Default {
+    fn update(&mut self, data: &[u8]);
+    fn finalize(self) -> [u8; 20];
+}
+
+impl Ripemd160Backend for ripemd::Ripemd160 {
+    fn update(&mut self, data: &[u8]) {
+        Digest::update(self, data);
+    }
+
+    fn finalize(self) -> [u8; 20] {
+        Digest::finalize(self).into()
+    }
+}
+
+// Incremental SHA-256 hasher
+#[derive(Clone, Default)]
+pub struct Sha256<B: Sha256Backend = sha2::Sha256>(B);
+
+impl Sha256 {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<B: Sha256Backend> Sha256<B> {
+    pub fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    pub fn finalize(self) -> [u8; 32] {
+        self.0.finalize()
+    }
+}
+
+// One-shot SHA-256
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    hasher.finalize()
+}
+
+// Incremental HMAC-SHA256
+#[derive(Clone)]
+pub struct HmacSha256<B: Sha256Backend = sha2::Sha256> {
+    inner: Sha256<B>,
+    outer: Sha256<B>,
+}
+
+impl HmacSha256 {
+    pub fn new(key: &[u8]) -> Self {
+        Self::from_key(key)
+    }
+}
+
+impl<B: Sha256Backend> HmacSha256<B> {
+    // new, for any backend
+    pub fn from_key(key: &[u8]) -> Self {
+        // Keys longer than the block size are hashed first
+        let mut block = [0u8; 64];
+        if key.len() > 64 {
+            let mut hasher = Sha256::<B>::default();
+            hasher.update(key);
+            block[..32].copy_from_slice(&hasher.finalize());
+        } else {
+            block[..key.len()].copy_from_slice(key);
+        }
+
+        let mut inner = Sha256::<B>::default();
+        let mut outer = Sha256::<B>::default();
+        inner.update(&block.map(|b| b ^ 0x36));
+        outer.update(&block.map(|b| b ^ 0x5c));
+        HmacSha256 { inner, outer }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    pub fn finalize(self) -> [u8; 32] {
+        let mut outer = self.outer;
+        outer.update(&self.inner.finalize());
+        outer.finalize()
+    }
+}
+
+// One-shot HMAC-SHA256
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut mac = HmacSha256::new(key);
+    mac.update(data);
+    mac.finalize()
+}
+
+// Incremental RIPEMD-160 hasher
+#[derive(Clone, Default)]
+pub struct Ripemd160<B: Ripemd160Backend = ripemd::Ripemd160>(B);
+
+impl Ripemd160 {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<B: Ripemd160Backend> Ripemd160<B> {
+    pub fn update(&mut self, data: &[u8]) {
+        self.0.update(data);
+    }
+
+    pub fn finalize(self) -> [u8; 20] {
+        self.0.finalize()
+    }
+}
+
+// One-shot RIPEMD-160
+pub fn ripemd160(data: &[u8]) -> [u8; 20] {
+    let mut hasher = Ripemd160::new();
+    hasher.update(data);
+    hasher.finalize()
+}
+
+// SHA256(SHA256(data)), Bitcoin's standard hash for transactions and
+// signed messages
+pub fn sha256d(data: &[u8]) -> [u8; 32] {
+    sha256(&sha256(data))
+}
+
+// Prefix for messages signed with a Bitcoin key, itself length-prefixed
+const SIGNED_MESSAGE_MAGIC: &[u8] = b"\x18Bitcoin Signed Message:\n";
+
+// The hash a Bitcoin wallet signs for a text message:
+// sha256d(magic || compact_size(len) || msg)
+pub fn bitcoin_signed_message_hash(msg: &str) -> [u8; 32] {
+    let msg = msg.as_bytes();
+    // CompactSize: one byte below 0xfd, else a marker and a little-endian
+    // u16, u32 or u64
+    let len = msg.len() as u64;
+    let mut prefix = [0u8; 9];
+    let prefix_len = match len {
+        0..=0xfc => {
+            prefix[0] = len as u8;
+            1
+        }
+        0xfd..=0xffff => {
+            prefix[0] = 0xfd;
+            prefix[1..3].copy_from_slice(&(len as u16).to_le_bytes());
+            3
+        }
+        0x10000..=0xffff_ffff => {
+            prefix[0] = 0xfe;
+            prefix[1..5].copy_from_slice(&(len as u32).to_le_bytes());
+            5
+        }
+        _ => {
+            prefix[0] = 0xff;
+            prefix[1..].copy_from_slice(&len.to_le_bytes());
+            9
+        }
+    };
+
+    let mut hasher = Sha256::new();
+    hasher.update(SIGNED_MESSAGE_MAGIC);
+    hasher.update(&prefix[..prefix_len]);
+    hasher.update(msg);
+    sha256(&hasher.finalize())
+}
+
+// RIPEMD160(SHA256(data)), the hash behind Bitcoin addresses
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    ripemd160(&sha256(data))
+}
+
+// Tags this crate hashes with on every signature or tweak. With std their
+// primed hashers are built once and cloned, which skips the tag hash and
+// one compression per call.
+#[cfg(feature = "std")]
+const CACHED_TAGS: [&str; 4] = ["BIP0340/aux", "BIP0340/nonce", "BIP0340/challenge", "TapTweak"];
+
+// A fresh hasher that has absorbed SHA256(tag) || SHA256(tag)
+fn primed_hasher(tag: &str) -> Sha256 {
+    let tag_hash = sha256(tag.as_bytes());
+    let mut hasher = Sha256::new();
+    hasher.update(&tag_hash);
+    hasher.update(&tag_hash);
+    hasher
+}
+
+// Hasher primed for a BIP340 tagged hash: SHA256(tag) || SHA256(tag) has
+// already been absorbed, so only the message remains to be fed in
+pub fn tagged_hasher(tag: &str) -> Sha256 {
+    #[cfg(feature = "std")]
+    if let Some(i) = CACHED_TAGS.iter().position(|known| *known == tag) {
+        static PRIMED: std::sync::OnceLock<[Sha256; 4]> = std::sync::OnceLock::new();
+        return PRIMED.get_or_init(|| CACHED_TAGS.map(primed_hasher))[i].clone();
+    }
+    primed_hasher(tag)
+}
+
+// BIP340 tagged hash: SHA256(SHA256(tag) || SHA256(tag) || data)
+pub fn tagged_hash(tag: &str, data: &[u8]) -> [u8; 32] {
+    let mut hasher = tagged_hasher(tag);
+    hasher.update(data);
+    hasher.finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    #[test]
+    fn test_sha256_vectors() {
+        assert_eq!(sha256(b""), hex::decode32("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"));
+        assert_eq!(sha256(b"abc"), hex::decode32("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"));
+        assert_eq!(
+            sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            hex::decode32("248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1")
+        );
+        assert_eq!(
+            sha256(&[b'a'; 1_000_000]),
+            hex::decode32("cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0")
+        );
+    }
+
+    #[test]
+    fn test_sha256_incremental() {
+        let data: Vec<u8> = (0..300u32).map(|i| i as u8).collect();
+        for split in [0, 1, 55, 56, 63, 64, 65, 128, 299] {
+            let mut hasher = Sha256::new();
+            hasher.update(&data[..split]);
+            hasher.update(&data[split..]);
+            assert_eq!(hasher.finalize(), sha256(&data));
+        }
+    }
+
+    // Stands in for another SHA-256 implementation
+    #[derive(Clone, Default)]
+    struct OtherBackend(sha2::Sha256);
+
+    impl Sha256Backend for OtherBackend {
+        fn update(&mut self, data: &[u8]) {
+            Sha256Backend::update(&mut self.0, data);
+        }
+
+        fn finalize(self) -> [u8; 32] {
+            Sha256Backend::finalize(self.0)
+        }
+    }
+
+    #[test]
+    fn test_backend_type_parameter() {
+        let mut hasher = Sha256::<OtherBackend>::default();
+        hasher.update(b"abc");
+        assert_eq!(hasher.finalize(), sha256(b"abc"));
+        let mut mac = HmacSha256::<OtherBackend>::from_key(&[0xaa; 131]);
+        mac.update(b"data");
+        assert_eq!(mac.finalize(), hmac_sha256(&[0xaa; 131], b"data"));
+    }
+
+    #[test]
+    fn test_hmac_sha256_vectors() {
+        // RFC 4231 test cases 1, 2 and 6
+        assert_eq!(
+            hmac_sha256(&[0x0b; 20], b"Hi There"),
+            hex::decode32("b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7")
+        );
+        assert_eq!(
+            hmac_sha256(b"Jefe", b"what do ya want for nothing?"),
+            hex::decode32("5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+        );
+        assert_eq!(
+            hmac_sha256(&[0xaa; 131], b"Test Using Larger Than Block-Size Key - Hash Key First"),
+            hex::decode32("60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54")
+        );
+    }
+
+    #[test]
+    fn test_tagged_hash() {
+        assert_eq!(
+            tagged_hash("BIP0340/challenge", b""),
+            hex::decode32("c216d352f5818b7b4beacd4ae0a26fe888080823d2a598856661bcd54f1b3713")
+        );
+        assert_eq!(
+            tagged_hash("BIP0340/challenge", &[0u8; 96]),
+            hex::decode32("9b44352d31b10c501fa0ae1ba915d94e8c4cfbb10cd92017468ca4d107a12d2d")
+        );
+        // A tag without a cached hasher is primed on the spot
+        assert_eq!(
+            tagged_hash("Custom tag", b"abc"),
+            hex::decode32("1048958d343e56ef3f5780b5a9fa4ed8f98b84a4a72f6d66bab2b35527ec6a23")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_cached_tags() {
+        for tag in CACHED_TAGS {
+            let tag_hash = sha256(tag.as_bytes());
+            let mut data = tag_hash.to_vec();
+            data.extend_from_slice(&tag_hash);
+            data.extend_from_slice(b"message");
+            assert_eq!(tagged_hash(tag, b"message"), sha256(&data), "{}", tag);
+        }
+    }
+
+    #[test]
+    fn test_sha256d() {
+        assert_eq!(
+            sha256d(b"hello").to_vec(),
+            hex::decode("9595c9df90075148eb06860365df33584b75bff782a510c6cd4883a419833d50").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_hash160() {
+        // HASH160 of the generator's compressed and uncompressed encodings,
+        // the payloads of the addresses 1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH
+        // and 1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm
+        let compressed = hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").unwrap();
+        assert_eq!(hash160(&compressed).to_vec(), hex::decode("751e76e8199196d454941c45d1b3a323f1433bd6").unwrap());
+        let uncompressed = hex::decode(
+            "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+             483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+        )
+        .unwrap();
+        assert_eq!(hash160(&uncompressed).to_vec(), hex::decode("91b24bf9f5288532960ac687abb035127b1d28a5").unwrap());
+    }
+
+    #[test]
+    fn test_bitcoin_signed_message_hash() {
+        let vectors = [
+            ("", "80e795d4a4caadd7047af389d9f7f220562feb6196032e2131e10563352c4bcc"),
+            ("vires is numeris", "88630588cd15244c180c7dee585b64278907703fd086e8f4cebec2daf3de28d3"),
+            (
+                "This is an example of a signed message.",
+                "d0e5595ac689a1df9f0b13443e0efd876eeb762d50a05f7179b1506bfccfeec5",
+            ),
+        ];
+        for (msg, expected) in vectors {
+            assert_eq!(bitcoin_signed_message_hash(msg).to_vec(), hex::decode(expected).unwrap());
+        }
+
+        // 300 bytes needs the three-byte 0xfd length prefix
+        let long = "a".repeat(300);
+        assert_eq!(
+            bitcoin_signed_message_hash(&long).to_vec(),
+            hex::decode("3ec158a43b80359df647352dac1d37dbf26a94e5f06e5790760290c75cd11dc0").unwrap()
+        );
+        let mut data = SIGNED_MESSAGE_MAGIC.to_vec();
+        data.extend_from_slice(&[0xfd, 0x2c, 0x01]);
+        data.extend_from_slice(long.as_bytes());
+        assert_eq!(bitcoin_signed_message_hash(&long), sha256d(&data));
+    }
+
+    #[test]
+    fn test_ripemd160_vectors() {
+        // From the RIPEMD-160 reference page
+        let vectors: [(&[u8], &str); 7] = [
+            (b"", "9c1185a5c5e9fc54612808977ee8f548b2258d31"),
+            (b"a", "0bdc9d2d256b3ee9daae347be6f4dc835a467ffe"),
+            (b"abc", "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"),
+            (b"message digest", "5d0689ef49d2fae572b881b123a85ffa21595f36"),
+            (b"abcdefghijklmnopqrstuvwxyz", "f71c27109c692c1b56bbdceb5b9d2865b3708dbc"),
+            (
+                b"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "9b752e45573d4b39f4dbd3323cab82bf63326bfb",
+            ),
+            (&[b'a'; 1_000_000], "52783243c1697bdbe16d37f97f68f08325dc1528"),
+        ];
+        for (data, expected) in vectors {
+            assert_eq!(ripemd160(data).to_vec(), hex::decode(expected).unwrap());
+        }
+        let mut hasher = Ripemd160::new();
+        hasher.update(b"message ");
+        hasher.update(b"digest");
+        assert_eq!(hasher.finalize(), ripemd160(b"message digest"));
+    }
+}
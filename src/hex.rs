@@ -0,0 +1,61 @@
+// Minimal hex encoding and decoding, for parsing, test vectors and serde
+
+#[cfg(any(test, feature = "serde"))]
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::Error;
+
+#[cfg(any(test, feature = "serde"))]
+const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+// Lowercase hex encoding
+#[cfg(any(test, feature = "serde"))]
+pub(crate) fn encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        out.push(DIGITS[(byte >> 4) as usize] as char);
+        out.push(DIGITS[(byte & 0xF) as usize] as char);
+    }
+    out
+}
+
+// Decode a hex string, returning None on odd length or a non-hex digit
+pub(crate) fn decode(s: &str) -> Option<Vec<u8>> {
+    // from_str_radix alone would also accept a leading '+'
+    if !s.len().is_multiple_of(2) || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok())
+        .collect()
+}
+
+// Decode exactly 64 hex digits into 32 bytes
+pub(crate) fn decode32_checked(s: &str) -> Result<[u8; 32], Error> {
+    let bytes = decode(s).ok_or(Error::InvalidEncoding)?;
+    bytes.try_into().map_err(|_| Error::InvalidEncoding)
+}
+
+// Decode exactly 32 bytes of hex, panicking otherwise
+#[cfg(test)]
+pub(crate) fn decode32(s: &str) -> [u8; 32] {
+    let bytes = decode(s).expect("invalid hex");
+    bytes.try_into().expect("expected 32 bytes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_round_trip() {
+        assert_eq!(encode(&[0x00, 0x7f, 0xab, 0xff]), "007fabff");
+        assert_eq!(decode("007fABff"), Some(vec![0x00, 0x7f, 0xab, 0xff]));
+        assert_eq!(decode(""), Some(vec![]));
+        for bad in ["0", "0g", "+1", " 1", "é0"] {
+            assert_eq!(decode(bad), None, "{:?}", bad);
+        }
+    }
+}
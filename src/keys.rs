@@ -0,0 +1,1002 @@
+#[cfg(feature = "hashes")]
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use rand_core::{CryptoRng, RngCore};
+
+#[cfg(feature = "hashes")]
+use crate::base58;
+#[cfg(feature = "hashes")]
+use crate::hashes::{hash160, tagged_hasher};
+use crate::{Error, FieldElement, Point, Scalar};
+
+// Derive the public key d·G for a secret scalar d
+pub fn public_key(secret: &Scalar) -> Result<Point, Error> {
+    // d = 0 would give the point at infinity, which is not a usable public key
+    if secret.is_zero() {
+        return Err(Error::ZeroScalar);
+    }
+    Ok(Point::mul_generator(secret))
+}
+
+// A private key together with its public key
+#[derive(Clone)]
+pub struct Keypair {
+    secret: PrivateKey,
+    public: PublicKey,
+}
+
+// The public key determines the secret, so equality compares the public
+// halves only and never looks at the secret
+impl PartialEq for Keypair {
+    fn eq(&self, other: &Self) -> bool {
+        self.public == other.public
+    }
+}
+
+impl Eq for Keypair {}
+
+// Only the public key is printed, so a keypair in a log line or a panic
+// message does not leak its secret
+impl fmt::Debug for Keypair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Keypair").field("public", &self.public).finish_non_exhaustive()
+    }
+}
+
+impl Keypair {
+    // Build a keypair from a secret scalar, rejecting the degenerate secrets
+    // 0 (public key at infinity) and 1 (public key equal to the generator)
+    pub fn from_secret(secret: Scalar) -> Result<Keypair, Error> {
+        if secret == Scalar::new([1, 0, 0, 0]) {
+            return Err(Error::WeakKey);
+        }
+        let public = PublicKey(public_key(&secret)?);
+        Ok(Keypair {
+            secret: PrivateKey(secret),
+            public,
+        })
+    }
+
+    // Generate a fresh keypair from a uniformly random private key
+    pub fn random<R: CryptoRng + RngCore>(rng: &mut R) -> Keypair {
+        let secret = PrivateKey::random(rng);
+        let public = secret.public_key();
+        Keypair { secret, public }
+    }
+
+    pub fn secret(&self) -> &PrivateKey {
+        &self.secret
+    }
+
+    pub fn public(&self) -> &PublicKey {
+        &self.public
+    }
+}
+
+// WIF version bytes
+#[cfg(feature = "hashes")]
+const WIF_MAINNET: u8 = 0x80;
+#[cfg(feature = "hashes")]
+const WIF_TESTNET: u8 = 0xef;
+
+// P2PKH address version bytes
+#[cfg(feature = "hashes")]
+const P2PKH_MAINNET: u8 = 0x00;
+#[cfg(feature = "hashes")]
+const P2PKH_TESTNET: u8 = 0x6f;
+
+// Secret key: a scalar in [1, n-1]. Not Copy, so that with the zeroize
+// feature every copy is an explicit clone that is itself wiped on drop.
+#[derive(Clone)]
+pub struct PrivateKey(Scalar);
+
+// Redacted, so that {:?} in a log line or an unwrap message never prints
+// the secret
+impl fmt::Debug for PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PrivateKey(..)")
+    }
+}
+
+// Keys are compared in constant time
+impl PartialEq for PrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl Eq for PrivateKey {}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for PrivateKey {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for PrivateKey {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for PrivateKey {}
+
+impl PrivateKey {
+    // Parse a 32-byte big-endian secret, rejecting zero and values >= n
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<PrivateKey, Error> {
+        let scalar = Scalar::from_bytes(bytes)?;
+        if scalar.is_zero() {
+            return Err(Error::ZeroScalar);
+        }
+        Ok(PrivateKey(scalar))
+    }
+
+    // Sample uniformly from [1, n-1]. Each 32-byte draw that is zero or >= n
+    // is discarded and redrawn rather than reduced mod n, which would make
+    // small values slightly more likely. A draw is rejected with probability
+    // about 2^-128, so the loop virtually always ends after one iteration.
+    // Any cryptographically secure generator can be plugged in.
+    pub fn random<R: CryptoRng + RngCore>(rng: &mut R) -> PrivateKey {
+        loop {
+            let mut bytes = [0u8; 32];
+            rng.fill_bytes(&mut bytes);
+            if let Ok(key) = PrivateKey::from_bytes(&bytes) {
+                return key;
+            }
+        }
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0.to_bytes()
+    }
+
+    pub fn as_scalar(&self) -> &Scalar {
+        &self.0
+    }
+
+    // Wallet Import Format: Base58Check of a version byte (0x80 mainnet, 0xef
+    // testnet), the 32 key bytes and, for keys whose public key is used
+    // compressed, a trailing 0x01
+    #[cfg(feature = "hashes")]
+    pub fn to_wif(&self, compressed: bool, mainnet: bool) -> String {
+        let mut payload = Vec::with_capacity(34);
+        payload.push(if mainnet { WIF_MAINNET } else { WIF_TESTNET });
+        payload.extend_from_slice(&self.to_bytes());
+        if compressed {
+            payload.push(0x01);
+        }
+        base58::encode_check(&payload)
+    }
+
+    // Parse a WIF string from either network, returning the key and whether
+    // it is marked compressed
+    #[cfg(feature = "hashes")]
+    pub fn from_wif(wif: &str) -> Result<(PrivateKey, bool), Error> {
+        let payload = base58::decode_check(wif)?;
+        let compressed = match payload.len() {
+            33 => false,
+            34 if payload[33] == 0x01 => true,
+            _ => return Err(Error::InvalidEncoding),
+        };
+        if payload[0] != WIF_MAINNET && payload[0] != WIF_TESTNET {
+            return Err(Error::InvalidEncoding);
+        }
+        let key = PrivateKey::from_bytes(payload[1..33].try_into().unwrap())?;
+        Ok((key, compressed))
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        // The scalar is non-zero, so d·G is never the point at infinity
+        PublicKey(Point::mul_generator(&self.0))
+    }
+
+    // d + t mod n, as in BIP32 child key derivation. Fails with ZeroScalar
+    // if the sum is zero.
+    pub fn tweak_add(&self, tweak: &Scalar) -> Result<PrivateKey, Error> {
+        let sum = self.0 + *tweak;
+        if sum.is_zero() {
+            return Err(Error::ZeroScalar);
+        }
+        Ok(PrivateKey(sum))
+    }
+
+    // d·t mod n. n is prime, so the product is zero only for t = 0, which
+    // is rejected with ZeroScalar.
+    pub fn tweak_mul(&self, tweak: &Scalar) -> Result<PrivateKey, Error> {
+        if tweak.is_zero() {
+            return Err(Error::ZeroScalar);
+        }
+        Ok(PrivateKey(self.0 * *tweak))
+    }
+}
+
+// Public key: a point on the curve other than the point at infinity
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PublicKey(Point);
+
+impl PublicKey {
+    // Parse a 33-byte compressed or 65-byte uncompressed SEC1 public key.
+    // Meant for untrusted input: any other length or prefix (including the
+    // one-byte encoding of infinity and the hybrid 0x06/0x07 forms) is
+    // InvalidEncoding, so is a coordinate >= p, and a point that is not on
+    // the curve is NotOnCurve. It never panics.
+    pub fn from_slice(bytes: &[u8]) -> Result<PublicKey, Error> {
+        let pubkey = PublicKey(Point::from_sec1(bytes)?);
+        pubkey.verify_valid()?;
+        Ok(pubkey)
+    }
+
+    // Check the key is a usable point: not infinity (PointAtInfinity) and on
+    // the curve (NotOnCurve). The group has prime order, so there are no
+    // other small-order points to exclude. Every constructor already ensures
+    // this; ecdh re-checks before using the key with a secret.
+    pub fn verify_valid(&self) -> Result<(), Error> {
+        validate_public_point(&self.0)
+    }
+
+    // SEC1 encoding, 33 bytes when compressed and 65 otherwise
+    pub fn serialize(&self, compressed: bool) -> Vec<u8> {
+        self.0.serialize(compressed)
+    }
+
+    pub fn as_point(&self) -> &Point {
+        &self.0
+    }
+
+    // Q + t·G, the public half of PrivateKey::tweak_add. Fails with
+    // PointAtInfinity when t is the negated private key.
+    pub fn tweak_add(&self, tweak: &Scalar) -> Result<PublicKey, Error> {
+        let sum = self.0.add(&Point::mul_generator(tweak));
+        if sum.is_infinity() {
+            return Err(Error::PointAtInfinity);
+        }
+        Ok(PublicKey(sum))
+    }
+
+    // t·Q, the public half of PrivateKey::tweak_mul; t = 0 is ZeroScalar
+    pub fn tweak_mul(&self, tweak: &Scalar) -> Result<PublicKey, Error> {
+        if tweak.is_zero() {
+            return Err(Error::ZeroScalar);
+        }
+        Ok(PublicKey(self.0.scalar_mul(&tweak.value)))
+    }
+
+    // P_1 + ... + P_m by plain point addition, the public key of the summed
+    // secrets in an n-of-n setup. Unlike aggregate_keys nothing is weighted,
+    // so it offers no protection against rogue-key attacks. An empty list,
+    // or keys that cancel out, is PointAtInfinity.
+    pub fn combine(keys: &[PublicKey]) -> Result<PublicKey, Error> {
+        let sum = keys.iter().fold(Point::infinity(), |acc, key| acc.add(&key.0));
+        if sum.is_infinity() {
+            return Err(Error::PointAtInfinity);
+        }
+        Ok(PublicKey(sum))
+    }
+
+    // Pay-to-public-key-hash address: Base58Check of a version byte (0x00
+    // mainnet, 0x6f testnet) and HASH160 of the SEC1 encoding. The compressed
+    // and uncompressed encodings give two different addresses for one key.
+    #[cfg(feature = "hashes")]
+    pub fn p2pkh_address(&self, compressed: bool, mainnet: bool) -> String {
+        let mut payload = Vec::with_capacity(21);
+        payload.push(if mainnet { P2PKH_MAINNET } else { P2PKH_TESTNET });
+        payload.extend_from_slice(&hash160(&self.serialize(compressed)));
+        base58::encode_check(&payload)
+    }
+}
+
+// Ordered by compressed encoding, byte by byte, as BIP327 KeySort sorts
+impl Ord for PublicKey {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.serialize(true).cmp(&other.serialize(true))
+    }
+}
+
+impl PartialOrd for PublicKey {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// The checks behind PublicKey::verify_valid, for callers holding a bare Point
+pub(crate) fn validate_public_point(point: &Point) -> Result<(), Error> {
+    match (point.x, point.y) {
+        (None, _) => Err(Error::PointAtInfinity),
+        (Some(_), Some(_)) if point.is_on_curve() => Ok(()),
+        _ => Err(Error::NotOnCurve),
+    }
+}
+
+// BIP340 x-only public key: the 32-byte x coordinate, standing for the point
+// with that x and an even y. Any 32 bytes can be held; lift_x says whether
+// they name a point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct XOnlyPublicKey([u8; 32]);
+
+impl XOnlyPublicKey {
+    pub fn from_bytes(bytes: &[u8; 32]) -> XOnlyPublicKey {
+        XOnlyPublicKey(*bytes)
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+
+    // The even-y point with this x, or None if x >= p or x³ + 7 is not a square
+    pub fn lift_x(&self) -> Option<Point> {
+        let x = FieldElement::from_bytes(&self.0).ok()?;
+        Point::from_x(x, false)
+    }
+
+    // BIP341 output key Q = P + t·G with t = tagged_hash("TapTweak",
+    // P.x || merkle_root), where P is this key lifted to even y. A key-path
+    // only output has no script tree and hashes P.x alone. Returns Q as an
+    // x-only key with the parity of its y, which goes in the control block.
+    // Fails if this key cannot be lifted (NotOnCurve), if t >= n
+    // (ScalarOutOfRange) or if Q is infinity; the last two happen with
+    // negligible probability.
+    #[cfg(feature = "hashes")]
+    pub fn tap_tweak(&self, merkle_root: Option<[u8; 32]>) -> Result<(XOnlyPublicKey, Parity), Error> {
+        let internal = self.lift_x().ok_or(Error::NotOnCurve)?;
+        let mut hasher = tagged_hasher("TapTweak");
+        hasher.update(&self.0);
+        if let Some(root) = merkle_root {
+            hasher.update(&root);
+        }
+        let tweak = Scalar::from_bytes(&hasher.finalize())?;
+
+        let output = PublicKey(internal).tweak_add(&tweak)?;
+        let parity = if output.0.y.unwrap().is_odd() {
+            Parity::Odd
+        } else {
+            Parity::Even
+        };
+        Ok((XOnlyPublicKey::from(output), parity))
+    }
+}
+
+// Parity of the y coordinate that an x-only key drops
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Parity {
+    Even,
+    Odd,
+}
+
+impl From<PublicKey> for XOnlyPublicKey {
+    // Drops the y parity: P and -P map to the same x-only key
+    fn from(pubkey: PublicKey) -> XOnlyPublicKey {
+        // A PublicKey is never the point at infinity
+        XOnlyPublicKey(pubkey.0.x.unwrap().to_bytes())
+    }
+}
+
+// Key aggregation from MuSig2 (BIP327): Q = Σ a_i·P_i, where
+// L = tagged_hash("KeyAgg list", P_1 || ... || P_u) over the compressed keys
+// and a_i = tagged_hash("KeyAgg coefficient", L || P_i) mod n, except that
+// the first key differing from P_1 gets a_i = 1. The keys are sorted first
+// (BIP327 KeySort), so the result does not depend on their order. Only the
+// aggregate key is computed; there is no signing protocol here. An empty
+// list, or keys that cancel out, is PointAtInfinity.
+#[cfg(feature = "hashes")]
+pub fn aggregate_keys(keys: &[PublicKey]) -> Result<PublicKey, Error> {
+    let mut sorted = keys.to_vec();
+    sorted.sort();
+    key_agg(&sorted)
+}
+
+// BIP327 KeyAgg on the keys in the given order
+#[cfg(feature = "hashes")]
+fn key_agg(keys: &[PublicKey]) -> Result<PublicKey, Error> {
+    let encodings: Vec<Vec<u8>> = keys.iter().map(|key| key.serialize(true)).collect();
+    let mut list_hasher = tagged_hasher("KeyAgg list");
+    for encoding in &encodings {
+        list_hasher.update(encoding);
+    }
+    let list_hash = list_hasher.finalize();
+    let second = encodings.iter().find(|encoding| Some(*encoding) != encodings.first());
+
+    let mut aggregate = Point::infinity();
+    for (key, encoding) in keys.iter().zip(&encodings) {
+        let coefficient = if Some(encoding) == second {
+            Scalar::from(1)
+        } else {
+            let mut hasher = tagged_hasher("KeyAgg coefficient");
+            hasher.update(&list_hash);
+            hasher.update(encoding);
+            Scalar::from_bytes_reduced(&hasher.finalize())
+        };
+        aggregate = aggregate.add(&(key.0 * coefficient));
+    }
+    if aggregate.is_infinity() {
+        return Err(Error::PointAtInfinity);
+    }
+    Ok(PublicKey(aggregate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{geq_limbs, hex, limbs_from_bytes, CURVE_ORDER};
+    use crate::test_vectors::test_rng;
+
+    #[test]
+    fn test_zero_secret_rejected() {
+        let zero = Scalar::new([0, 0, 0, 0]);
+        assert_eq!(public_key(&zero), Err(Error::ZeroScalar));
+        assert_eq!(Keypair::from_secret(zero), Err(Error::ZeroScalar));
+
+        // n reduces to zero and must be rejected the same way
+        let order = Scalar::new(crate::CURVE_ORDER);
+        assert_eq!(Keypair::from_secret(order), Err(Error::ZeroScalar));
+    }
+
+    #[test]
+    fn test_secret_one_is_weak() {
+        let one = Scalar::new([1, 0, 0, 0]);
+        // The raw derivation still works and yields G
+        assert_eq!(public_key(&one), Ok(Point::generator()));
+        // but a keypair built from it is refused
+        assert_eq!(Keypair::from_secret(one), Err(Error::WeakKey));
+    }
+
+    #[test]
+    fn test_keypair_from_secret() {
+        let secret = Scalar::new([2, 0, 0, 0]);
+        let keypair = Keypair::from_secret(secret).unwrap();
+        assert_eq!(*keypair.secret().as_scalar(), secret);
+        assert_eq!(*keypair.public().as_point(), Point::generator().add(&Point::generator()));
+        assert!(keypair.public().as_point().is_on_curve());
+
+        let debug = format!("{:?}", keypair);
+        assert_eq!(debug, format!("Keypair {{ public: {:?}, .. }}", keypair.public()));
+        assert!(!debug.contains("Scalar"));
+        assert_ne!(keypair, Keypair::from_secret(Scalar::new([3, 0, 0, 0])).unwrap());
+    }
+
+    // Replays fixed 32-byte blocks, to drive the rejection loop
+    struct ScriptedRng(Vec<[u8; 32]>);
+
+    impl CryptoRng for ScriptedRng {}
+
+    impl RngCore for ScriptedRng {
+        fn next_u32(&mut self) -> u32 {
+            rand_core::impls::next_u32_via_fill(self)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            rand_core::impls::next_u64_via_fill(self)
+        }
+
+        // Each call consumes one scripted draw, truncated to dest
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            let draw = self.0.remove(0);
+            dest.copy_from_slice(&draw[..dest.len()]);
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_random_keys_in_range() {
+        let mut rng = test_rng(42);
+        let mut seen = Vec::new();
+        for _ in 0..64 {
+            let key = PrivateKey::random(&mut rng);
+            assert!(!key.as_scalar().is_zero());
+            assert!(!geq_limbs(&key.as_scalar().value, &CURVE_ORDER));
+            assert!(!seen.contains(&key));
+            seen.push(key);
+        }
+
+        // Bits should be spread roughly evenly: 64 keys · 256 bits, half set
+        let ones: u32 = seen
+            .iter()
+            .flat_map(|k| k.to_bytes())
+            .map(|b| b.count_ones())
+            .sum();
+        assert!((7600..8800).contains(&ones), "{} bits set", ones);
+    }
+
+    #[test]
+    fn test_random_rejects_out_of_range_draws() {
+        let order = hex::decode32("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+        let mut expected = [0u8; 32];
+        expected[31] = 7;
+        let mut rng = ScriptedRng(vec![order, [0u8; 32], [0xFF; 32], expected]);
+        assert_eq!(PrivateKey::random(&mut rng).to_bytes(), expected);
+        assert!(rng.0.is_empty());
+    }
+
+    #[test]
+    fn test_random_keypair() {
+        let mut rng = test_rng(7);
+        for _ in 0..4 {
+            let keypair = Keypair::random(&mut rng);
+            assert!(keypair.public().as_point().is_on_curve());
+            assert_eq!(keypair.secret().public_key(), *keypair.public());
+        }
+    }
+
+    #[test]
+    fn test_seeded_chacha_keypair_is_stable() {
+        use rand_chacha::rand_core::SeedableRng;
+        use rand_chacha::ChaCha20Rng;
+
+        // The first key is the first 32 bytes of the ChaCha20 keystream for
+        // the all-zero key and nonce (RFC 7539 section A.1, test vector 1)
+        let mut rng = ChaCha20Rng::from_seed([0u8; 32]);
+        let keypair = Keypair::random(&mut rng);
+        assert_eq!(
+            keypair.secret().to_bytes(),
+            hex::decode32("76b8e0ada0f13d90405d6ae55386bd28bdd219b8a08ded1aa836efcc8b770dc7")
+        );
+
+        let mut again = ChaCha20Rng::from_seed([0u8; 32]);
+        assert_eq!(Keypair::random(&mut again), keypair);
+        assert_eq!(Keypair::random(&mut again), Keypair::random(&mut rng));
+    }
+
+    #[test]
+    fn test_private_key_one_is_generator() {
+        let mut bytes = [0u8; 32];
+        bytes[31] = 1;
+        let privkey = PrivateKey::from_bytes(&bytes).unwrap();
+        let pubkey = privkey.public_key();
+        assert_eq!(*pubkey.as_point(), Point::generator());
+        assert_eq!(
+            pubkey.serialize(true),
+            hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798").unwrap()
+        );
+        assert_eq!(
+            pubkey.serialize(false),
+            hex::decode(
+                "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+                 483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8"
+            )
+            .unwrap()
+        );
+        assert_eq!(privkey.to_bytes(), bytes);
+        assert_eq!(format!("{:?}", privkey), "PrivateKey(..)");
+    }
+
+    #[test]
+    fn test_private_key_range() {
+        assert_eq!(PrivateKey::from_bytes(&[0u8; 32]), Err(Error::ZeroScalar));
+        let order = hex::decode32("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+        assert_eq!(PrivateKey::from_bytes(&order), Err(Error::ScalarOutOfRange));
+        assert_eq!(PrivateKey::from_bytes(&[0xFF; 32]), Err(Error::ScalarOutOfRange));
+        let max = hex::decode32("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140");
+        assert!(PrivateKey::from_bytes(&max).is_ok());
+    }
+
+    #[test]
+    fn test_public_key_round_trip() {
+        let privkey = PrivateKey::from_bytes(&hex::decode32(
+            "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721",
+        ))
+        .unwrap();
+        let pubkey = privkey.public_key();
+        for compressed in [true, false] {
+            let bytes = pubkey.serialize(compressed);
+            assert_eq!(bytes.len(), if compressed { 33 } else { 65 });
+            assert_eq!(PublicKey::from_slice(&bytes), Ok(pubkey));
+        }
+    }
+
+    #[test]
+    fn test_public_key_rejects_bad_encodings() {
+        let mut bytes = Point::generator().serialize(false);
+        assert_eq!(PublicKey::from_slice(&bytes[..64]), Err(Error::InvalidEncoding));
+
+        bytes[64] ^= 1;
+        assert_eq!(PublicKey::from_slice(&bytes), Err(Error::NotOnCurve));
+
+        let mut compressed = Point::generator().serialize(true);
+        compressed[0] = 0x05;
+        assert_eq!(PublicKey::from_slice(&compressed), Err(Error::InvalidEncoding));
+
+        // x = 5 has no matching y on the curve; x = p is not a field element
+        let mut no_root = [0u8; 33];
+        no_root[0] = 0x02;
+        no_root[32] = 5;
+        assert_eq!(PublicKey::from_slice(&no_root), Err(Error::NotOnCurve));
+        let mut too_big = [0xFFu8; 33];
+        too_big[0] = 0x02;
+        assert_eq!(PublicKey::from_slice(&too_big), Err(Error::InvalidEncoding));
+
+        // The SEC1 encoding of infinity is not a public key
+        assert_eq!(PublicKey::from_slice(&[0x00]), Err(Error::InvalidEncoding));
+    }
+
+    #[test]
+    #[cfg(feature = "hashes")]
+    fn test_verify_valid() {
+        assert_eq!(PublicKey(Point::generator()).verify_valid(), Ok(()));
+        assert_eq!(PublicKey(Point::infinity()).verify_valid(), Err(Error::PointAtInfinity));
+
+        let g = Point::generator();
+        let off_curve = Point { x: g.x, y: Some(g.y.unwrap() + FieldElement::from(1)) };
+        assert_eq!(PublicKey(off_curve).verify_valid(), Err(Error::NotOnCurve));
+        let no_y = Point { x: g.x, y: None };
+        assert_eq!(PublicKey(no_y).verify_valid(), Err(Error::NotOnCurve));
+
+        // ecdh checks before any scalar work
+        let privkey = PrivateKey::from_bytes(&[0x11; 32]).unwrap();
+        assert_eq!(crate::ecdh(&privkey, &PublicKey(Point::infinity())), Err(Error::PointAtInfinity));
+        assert_eq!(crate::ecdh(&privkey, &PublicKey(off_curve)), Err(Error::NotOnCurve));
+    }
+
+    #[test]
+    fn test_tweaks_commute_with_public_key() {
+        let mut rng = test_rng(0x7EA4);
+        for _ in 0..8 {
+            let privkey = PrivateKey::random(&mut rng);
+            let pubkey = privkey.public_key();
+            let tweak = *PrivateKey::random(&mut rng).as_scalar();
+
+            let added = privkey.tweak_add(&tweak).unwrap();
+            assert_eq!(added.public_key(), pubkey.tweak_add(&tweak).unwrap());
+            let multiplied = privkey.tweak_mul(&tweak).unwrap();
+            assert_eq!(multiplied.public_key(), pubkey.tweak_mul(&tweak).unwrap());
+        }
+        // A zero tweak_add is the identity
+        let privkey = PrivateKey::random(&mut rng);
+        let zero = Scalar::new([0, 0, 0, 0]);
+        assert_eq!(privkey.tweak_add(&zero), Ok(privkey.clone()));
+        assert_eq!(privkey.public_key().tweak_add(&zero), Ok(privkey.public_key()));
+    }
+
+    #[test]
+    fn test_tweaks_reject_degenerate_results() {
+        let privkey = PrivateKey::random(&mut test_rng(3));
+        let pubkey = privkey.public_key();
+        let negated = -*privkey.as_scalar();
+        assert_eq!(privkey.tweak_add(&negated), Err(Error::ZeroScalar));
+        assert_eq!(pubkey.tweak_add(&negated), Err(Error::PointAtInfinity));
+
+        let zero = Scalar::new([0, 0, 0, 0]);
+        assert_eq!(privkey.tweak_mul(&zero), Err(Error::ZeroScalar));
+        assert_eq!(pubkey.tweak_mul(&zero), Err(Error::ZeroScalar));
+    }
+
+    #[test]
+    fn test_combine() {
+        let mut rng = test_rng(0xB7E1_51A3);
+        let (a, b, c) = (PrivateKey::random(&mut rng), PrivateKey::random(&mut rng), PrivateKey::random(&mut rng));
+        let (p, q, r) = (a.public_key(), b.public_key(), c.public_key());
+        assert_eq!(PublicKey::combine(&[p, q]), Ok(PublicKey(p.0.add(&q.0))));
+        assert_eq!(PublicKey::combine(&[p]), Ok(p));
+
+        // The combined key belongs to the summed secrets, in any order
+        let secret = a.tweak_add(b.as_scalar()).unwrap().tweak_add(c.as_scalar()).unwrap();
+        let keys = [p, q, r];
+        assert_eq!(PublicKey::combine(&keys), Ok(secret.public_key()));
+        assert_eq!(PublicKey::combine(&[r, p, q]), Ok(secret.public_key()));
+
+        // A key and its negation cancel, and so does nothing at all
+        let negated = PrivateKey::from_bytes(&(-*a.as_scalar()).to_bytes()).unwrap();
+        assert_eq!(PublicKey::combine(&[p, negated.public_key()]), Err(Error::PointAtInfinity));
+        assert_eq!(PublicKey::combine(&[]), Err(Error::PointAtInfinity));
+    }
+
+    // Reference decision for from_slice, written against the SEC1 rules
+    // directly: Euler's criterion instead of sqrt for compressed keys
+    fn expected_parse(bytes: &[u8]) -> Result<(), Error> {
+        let coordinate = |range: core::ops::Range<usize>| {
+            let limbs = limbs_from_bytes(bytes[range].try_into().unwrap());
+            if geq_limbs(&limbs, &crate::FIELD_MODULUS) {
+                Err(Error::InvalidEncoding)
+            } else {
+                Ok(FieldElement::new(limbs))
+            }
+        };
+        let b = FieldElement::from(7);
+        match (bytes.len(), bytes.first()) {
+            (33, Some(0x02 | 0x03)) => {
+                let x = coordinate(1..33)?;
+                let rhs = x * x * x + b;
+                // (p - 1) / 2
+                let exp = [0xFFFFFFFF7FFFFE17, u64::MAX, u64::MAX, 0x7FFFFFFFFFFFFFFF];
+                if rhs.is_zero() || rhs.pow(&exp) == FieldElement::from(1) {
+                    Ok(())
+                } else {
+                    Err(Error::NotOnCurve)
+                }
+            }
+            (65, Some(0x04)) => {
+                let x = coordinate(1..33)?;
+                let y = coordinate(33..65)?;
+                if y * y == x * x * x + b {
+                    Ok(())
+                } else {
+                    Err(Error::NotOnCurve)
+                }
+            }
+            _ => Err(Error::InvalidEncoding),
+        }
+    }
+
+    #[test]
+    fn test_public_key_from_slice_fuzz() {
+        let mut rng = test_rng(0x5EC1);
+        let valid = [
+            Point::generator().serialize(true),
+            Point::generator().serialize(false),
+            PrivateKey::random(&mut rng).public_key().serialize(true),
+            PrivateKey::random(&mut rng).public_key().serialize(false),
+        ];
+
+        let mut inputs: Vec<Vec<u8>> = vec![vec![], vec![0x00], vec![0x02], vec![0x04]];
+        for len in 0..100usize {
+            for _ in 0..8 {
+                let mut bytes = vec![0u8; len];
+                rng.fill_bytes(&mut bytes);
+                // Give well-formed prefixes a fair chance
+                if let Some(first) = bytes.first_mut() {
+                    *first = [0x02, 0x03, 0x04, 0x06, 0x07, *first][(*first % 6) as usize];
+                }
+                inputs.push(bytes);
+            }
+        }
+        // Single-bit corruptions of valid keys, and hybrid prefixes
+        for encoding in &valid {
+            for bit in 0..encoding.len() * 8 {
+                let mut bytes = encoding.clone();
+                bytes[bit / 8] ^= 1 << (bit % 8);
+                inputs.push(bytes);
+            }
+            let mut hybrid = encoding.clone();
+            hybrid[0] = 0x06 | (hybrid[0] & 1);
+            inputs.push(hybrid);
+        }
+
+        let mut accepted = 0;
+        for bytes in &inputs {
+            crate::fuzz::point_from_slice(bytes);
+            let parsed = PublicKey::from_slice(bytes);
+            assert_eq!(parsed.map(|_| ()), expected_parse(bytes), "{:02x?}", bytes);
+            if let Ok(pubkey) = parsed {
+                accepted += 1;
+                assert!(pubkey.as_point().is_on_curve());
+                assert!(!pubkey.as_point().is_infinity());
+                assert_eq!(pubkey.serialize(bytes.len() == 33), *bytes);
+            }
+        }
+        for encoding in &valid {
+            assert!(PublicKey::from_slice(encoding).is_ok());
+        }
+        // Roughly half of the random compressed inputs name a point
+        assert!(accepted > 50, "{} accepted", accepted);
+    }
+
+    #[test]
+    fn test_x_only_lift_x() {
+        // Key from BIP340 test vector 3, whose public key d·G has odd y, so
+        // lifting its x gives -d·G
+        let privkey = PrivateKey::from_bytes(&hex::decode32(
+            "0b432b2677937381aef05bb02a66ecd012773062cf3fa2549e44f58ed2401710",
+        ))
+        .unwrap();
+        let pubkey = privkey.public_key();
+        let x_only = XOnlyPublicKey::from(pubkey);
+        assert_eq!(
+            x_only.to_bytes(),
+            hex::decode32("25d1dff95105f5253c4022f628a996ad3a0d95fbf21d468a1b33f8c160d8f517")
+        );
+
+        let lifted = x_only.lift_x().unwrap();
+        assert_eq!(lifted.x, pubkey.as_point().x);
+        assert!(!lifted.y.unwrap().is_odd());
+        assert!(pubkey.as_point().y.unwrap().is_odd());
+        assert_eq!(lifted.add(pubkey.as_point()), Point::infinity());
+    }
+
+    #[test]
+    fn test_x_only_lift_x_rejects_invalid() {
+        // x = 5 has no y on the curve; p and above are not field elements
+        let mut no_root = [0u8; 32];
+        no_root[31] = 5;
+        assert_eq!(XOnlyPublicKey::from_bytes(&no_root).lift_x(), None);
+        let p = hex::decode32("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f");
+        assert_eq!(XOnlyPublicKey::from_bytes(&p).lift_x(), None);
+        assert_eq!(XOnlyPublicKey::from_bytes(&[0xFF; 32]).lift_x(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "hashes")]
+    fn test_tap_tweak() {
+        // BIP341 wallet-test-vectors.json, scriptPubKey cases 0 (no script
+        // tree) and 1 (a single leaf, whose hash is the Merkle root)
+        let internal = XOnlyPublicKey::from_bytes(&hex::decode32(
+            "d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d",
+        ));
+        let (output, parity) = internal.tap_tweak(None).unwrap();
+        assert_eq!(
+            output.to_bytes(),
+            hex::decode32("53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343")
+        );
+        assert_eq!(parity, Parity::Odd);
+
+        let internal = XOnlyPublicKey::from_bytes(&hex::decode32(
+            "187791b6f712a8ea41c8ecdd0ee77fab3e85263b37e1ec18a3651926b3a6cf27",
+        ));
+        let merkle_root = hex::decode32("5b75adecf53548f3ec6ad7d78383bf84cc57b55a3127c72b9a2481752dd88b21");
+        let (output, parity) = internal.tap_tweak(Some(merkle_root)).unwrap();
+        assert_eq!(
+            output.to_bytes(),
+            hex::decode32("147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3")
+        );
+        // The vector's control block starts with 0xc1: leaf version 0xc0 | 1
+        assert_eq!(parity, Parity::Odd);
+
+        // Case 0 also publishes t, so check Q = P + t·G directly
+        let internal = XOnlyPublicKey::from_bytes(&hex::decode32(
+            "d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d",
+        ));
+        let tweak = Scalar::from_hex("b86e7be8f39bab32a6f2c0443abbc210f0edac0e2c53d501b36b64437d9c6c70").unwrap();
+        let expected = PublicKey(internal.lift_x().unwrap()).tweak_add(&tweak).unwrap();
+        assert_eq!(internal.tap_tweak(None).unwrap().0, XOnlyPublicKey::from(expected));
+
+        let mut no_root = [0u8; 32];
+        no_root[31] = 5;
+        assert_eq!(XOnlyPublicKey::from_bytes(&no_root).tap_tweak(None), Err(Error::NotOnCurve));
+    }
+
+    #[test]
+    #[cfg(feature = "hashes")]
+    fn test_aggregate_keys() {
+        // BIP327 key_agg_vectors.json
+        let keys: Vec<PublicKey> = [
+            "02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+            "03dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+            "023590a94e768f8e1815c2f24b4d80a8e3149316c3518ce7b7ad338368d038ca66",
+        ]
+        .iter()
+        .map(|key| PublicKey::from_slice(&hex::decode(key).unwrap()).unwrap())
+        .collect();
+        let cases: [(&[usize], &str); 4] = [
+            (&[0, 1, 2], "90539eede565f5d054f32cc0c220126889ed1e5d193baf15aef344fe59d4610c"),
+            (&[2, 1, 0], "6204de8b083426dc6eaf9502d27024d53fc826bf7d2012148a0575435df54b2b"),
+            (&[0, 0, 0], "b436e3bad62b8cd409969a224731c193d051162d8c5ae8b109306127da3aa935"),
+            (&[0, 0, 1, 1], "69bc22bfa5d106306e48a20679de1d7389386124d07571d0d872686028c26a3e"),
+        ];
+        for (indices, expected) in cases {
+            let ordered: Vec<PublicKey> = indices.iter().map(|&i| keys[i]).collect();
+            let aggregate = key_agg(&ordered).unwrap();
+            assert_eq!(XOnlyPublicKey::from(aggregate).to_bytes(), hex::decode32(expected));
+        }
+
+        // Sorting makes the order irrelevant; [2, 0, 1] is the sorted order
+        let sorted = key_agg(&[keys[2], keys[0], keys[1]]).unwrap();
+        for order in [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]] {
+            let permuted: Vec<PublicKey> = order.iter().map(|&i| keys[i]).collect();
+            let aggregate = aggregate_keys(&permuted).unwrap();
+            assert_eq!(aggregate, sorted);
+            assert!(aggregate.as_point().is_on_curve());
+        }
+
+        // A 2-of-2 of random keys, either way round
+        let mut rng = test_rng(0x2F2);
+        let a = PrivateKey::random(&mut rng).public_key();
+        let b = PrivateKey::random(&mut rng).public_key();
+        assert_eq!(aggregate_keys(&[a, b]), aggregate_keys(&[b, a]));
+        assert!(aggregate_keys(&[a, b]).unwrap().as_point().is_on_curve());
+
+        assert_eq!(aggregate_keys(&[]), Err(Error::PointAtInfinity));
+    }
+
+    #[test]
+    fn test_public_key_order_matches_encoding() {
+        let mut rng = test_rng(0x6C8E_9CF5);
+        let mut keys: Vec<PublicKey> = (0..8).map(|_| PrivateKey::random(&mut rng).public_key()).collect();
+        keys.push(keys[3]);
+        let mut encodings: Vec<Vec<u8>> = keys.iter().map(|key| key.serialize(true)).collect();
+        keys.sort();
+        encodings.sort();
+        assert_eq!(keys.iter().map(|key| key.serialize(true)).collect::<Vec<_>>(), encodings);
+
+        // The prefix byte decides first: this even-y key sorts before the
+        // odd-y one though its x is larger
+        let parse = |s: &str| PublicKey::from_slice(&hex::decode(s).unwrap()).unwrap();
+        let even = parse("02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9");
+        let odd = parse("03dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659");
+        assert!(even < odd);
+    }
+
+    #[test]
+    #[cfg(feature = "hashes")]
+    fn test_wif_round_trip() {
+        // Example key from the Bitcoin wiki's Wallet import format page
+        let privkey = PrivateKey::from_bytes(&hex::decode32(
+            "0c28fca386c7a227600b2fe50b7cae11ec86d3bf1fbe471be89827e19d72aa1d",
+        ))
+        .unwrap();
+        let vectors = [
+            (false, true, "5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ"),
+            (true, true, "KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617"),
+            (false, false, "91gGn1HgSap6CbU12F6z3pJri26xzp7Ay1VW6NHCoEayNXwRpu2"),
+            (true, false, "cMzLdeGd5vEqxB8B6VFQoRopQ3sLAAvEzDAoQgvX54xwofSWj1fx"),
+        ];
+        for (compressed, mainnet, wif) in vectors {
+            assert_eq!(privkey.to_wif(compressed, mainnet), wif);
+            assert_eq!(PrivateKey::from_wif(wif), Ok((privkey.clone(), compressed)));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "hashes")]
+    fn test_wif_rejects_bad_input() {
+        // Last character changed
+        assert_eq!(
+            PrivateKey::from_wif("5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTK"),
+            Err(Error::InvalidChecksum)
+        );
+        // '0' is not in the Base58 alphabet
+        assert_eq!(
+            PrivateKey::from_wif("0HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ"),
+            Err(Error::InvalidEncoding)
+        );
+        // Valid checksums over: version 0x70; compression flag 0x02; a zero key
+        assert_eq!(
+            PrivateKey::from_wif("4kojNZHdDYvL5QfRtdH5v6AWQXtmSP5y2qVq7LvMZ1jihWtoM86"),
+            Err(Error::InvalidEncoding)
+        );
+        assert_eq!(
+            PrivateKey::from_wif("KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvWxyf5d"),
+            Err(Error::InvalidEncoding)
+        );
+        assert_eq!(
+            PrivateKey::from_wif("5HpHagT65TZzG1PH3CSu63k8DbpvD8s5ip4nEB3kEsreAbuatmU"),
+            Err(Error::ZeroScalar)
+        );
+        assert_eq!(PrivateKey::from_wif(""), Err(Error::InvalidEncoding));
+    }
+
+    #[test]
+    #[cfg(feature = "hashes")]
+    fn test_p2pkh_address() {
+        // The Bitcoin wiki's example key, whose uncompressed address is
+        // 1GAehh7TsJAHuUAeKZcXf5CnwuGuGgyX2S
+        let pubkey = PrivateKey::from_bytes(&hex::decode32(
+            "0c28fca386c7a227600b2fe50b7cae11ec86d3bf1fbe471be89827e19d72aa1d",
+        ))
+        .unwrap()
+        .public_key();
+        assert_eq!(pubkey.p2pkh_address(false, true), "1GAehh7TsJAHuUAeKZcXf5CnwuGuGgyX2S");
+        assert_eq!(pubkey.p2pkh_address(true, true), "1LoVGDgRs9hTfTNJNuXKSpywcbdvwRXpmK");
+        assert_eq!(pubkey.p2pkh_address(false, false), "mvgbzkCSgKbYgaeG38auUzR7otscEGi8U7");
+
+        // The generator, i.e. private key 1
+        let generator = PublicKey(Point::generator());
+        assert_eq!(generator.p2pkh_address(true, true), "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH");
+        assert_eq!(generator.p2pkh_address(false, true), "1EHNa6Q4Jz2uvNExL497mE43ikXhwF6kZm");
+        assert_eq!(generator.p2pkh_address(true, false), "mrCDrCybB6J1vRfbwM5hemdJz73FwDBC8r");
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_private_key_zeroized() {
+        use core::mem::ManuallyDrop;
+        use zeroize::Zeroize;
+
+        let mut key = PrivateKey::random(&mut test_rng(1));
+        assert!(!key.as_scalar().is_zero());
+        key.zeroize();
+        assert!(key.as_scalar().is_zero());
+
+        // Run the destructor in place and inspect what it left behind; an
+        // all-zero Scalar is still a valid value to read
+        let mut key = ManuallyDrop::new(PrivateKey::random(&mut test_rng(2)));
+        assert!(!key.as_scalar().is_zero());
+        unsafe { ManuallyDrop::drop(&mut key) };
+        assert_eq!(key.0.value, [0u64; 4]);
+    }
+}
@@ -0,0 +1,185 @@
+// Constant-time scalar multiplication: a Montgomery ladder over the complete
+// addition formulas of Renes, Costello and Batina ("Complete addition
+// formulas for prime order elliptic curves", 2015, algorithms 7 and 9 with
+// a = 0). Complete means one formula handles every input pair, infinity
+// and P + P included, so unlike the Jacobian formulas there is nothing to
+// branch on.
+
+use crate::{FieldElement, JacobianPoint};
+
+// 3·b for b = 7
+const B3: u64 = 21;
+
+// Homogeneous projective coordinates: (X, Y, Z) is the affine point
+// (X/Z, Y/Z), and infinity is (0, 1, 0)
+#[derive(Clone, Copy)]
+struct ProjectivePoint {
+    x: FieldElement,
+    y: FieldElement,
+    z: FieldElement,
+}
+
+impl ProjectivePoint {
+    // (X, Y, Z) in Jacobian coordinates is (XZ, Y, Z³) here. Infinity is
+    // selected rather than branched to, so this is constant time too.
+    fn from_jacobian(p: &JacobianPoint) -> Self {
+        let infinity = p.z.ct_eq(&FieldElement::from(0)) as u8;
+        ProjectivePoint {
+            x: p.x * p.z,
+            y: FieldElement::conditional_select(&p.y, &FieldElement::from(1), infinity),
+            z: p.z.square() * p.z,
+        }
+    }
+
+    // And back: (X, Y, Z) is (XZ, YZ², Z) in Jacobian coordinates, with Z = 0
+    // still meaning infinity
+    fn to_jacobian(self) -> JacobianPoint {
+        JacobianPoint {
+            x: self.x * self.z,
+            y: self.y * self.z.square(),
+            z: self.z,
+        }
+    }
+
+    // Algorithm 7: 12 multiplications and 2 by 3b
+    fn add(&self, other: &Self) -> Self {
+        let b3 = FieldElement::from(B3);
+        let (x1, y1, z1) = (self.x, self.y, self.z);
+        let (x2, y2, z2) = (other.x, other.y, other.z);
+
+        let mut t0 = x1 * x2;
+        let mut t1 = y1 * y2;
+        let mut t2 = z1 * z2;
+        let t3 = (x1 + y1) * (x2 + y2) - (t0 + t1);
+        let t4 = (y1 + z1) * (y2 + z2) - (t1 + t2);
+        let mut y3 = (x1 + z1) * (x2 + z2) - (t0 + t2);
+        t0 = t0 + t0 + t0;
+        t2 = b3 * t2;
+        let mut z3 = t1 + t2;
+        t1 -= t2;
+        y3 = b3 * y3;
+        let x3 = t3 * t1 - t4 * y3;
+        y3 = t1 * z3 + y3 * t0;
+        z3 = z3 * t4 + t0 * t3;
+        ProjectivePoint {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    // Algorithm 9: 6 multiplications, 2 squarings and 1 by 3b
+    fn double(&self) -> Self {
+        let b3 = FieldElement::from(B3);
+        let (x, y, z) = (self.x, self.y, self.z);
+
+        let t0 = y.square();
+        let eight_t0 = {
+            let two = t0 + t0;
+            let four = two + two;
+            four + four
+        };
+        let t2 = b3 * z.square();
+        let x3 = t2 * eight_t0;
+        let y3 = t0 + t2;
+        let z3 = y * z * eight_t0;
+        let t0 = t0 - (t2 + t2 + t2);
+        let y3 = t0 * y3 + x3;
+        let xy = x * y;
+        let x3 = t0 * xy;
+        ProjectivePoint {
+            x: x3 + x3,
+            y: y3,
+            z: z3,
+        }
+    }
+
+    // Swap a and b if choice is 1, without branching
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: u8) {
+        let (old_a, old_b) = (*a, *b);
+        *a = ProjectivePoint {
+            x: FieldElement::conditional_select(&old_a.x, &old_b.x, choice),
+            y: FieldElement::conditional_select(&old_a.y, &old_b.y, choice),
+            z: FieldElement::conditional_select(&old_a.z, &old_b.z, choice),
+        };
+        *b = ProjectivePoint {
+            x: FieldElement::conditional_select(&old_b.x, &old_a.x, choice),
+            y: FieldElement::conditional_select(&old_b.y, &old_a.y, choice),
+            z: FieldElement::conditional_select(&old_b.z, &old_a.z, choice),
+        };
+    }
+}
+
+impl JacobianPoint {
+    // k·P by a Montgomery ladder over all 256 bits of scalar, most
+    // significant first. Constant time: every bit costs one complete
+    // addition and one complete doubling, the bit only decides a masked
+    // swap, and no memory access depends on it. Use this for secrets.
+    pub fn scalar_mul(&self, scalar: &[u64; 4]) -> Self {
+        // Invariant: r1 - r0 = P
+        let mut r0 = ProjectivePoint {
+            x: FieldElement::from(0),
+            y: FieldElement::from(1),
+            z: FieldElement::from(0),
+        };
+        let mut r1 = ProjectivePoint::from_jacobian(self);
+        for i in (0..256).rev() {
+            let bit = ((scalar[i / 64] >> (i % 64)) & 1) as u8;
+            ProjectivePoint::conditional_swap(&mut r0, &mut r1, bit);
+            r1 = r0.add(&r1);
+            r0 = r0.double();
+            ProjectivePoint::conditional_swap(&mut r0, &mut r1, bit);
+        }
+        r0.to_jacobian()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Point;
+
+    fn projective(p: &Point) -> ProjectivePoint {
+        ProjectivePoint::from_jacobian(&JacobianPoint::from_affine(p))
+    }
+
+    #[test]
+    fn test_complete_formulas() {
+        let g = Point::generator();
+        let q = g.double().add(&g);
+        let infinity = projective(&Point::infinity());
+
+        // The cases the Jacobian formulas branch on all go through one formula
+        let (pg, pq) = (projective(&g), projective(&q));
+        assert_eq!(pg.add(&pq).to_jacobian().to_affine(), g.add(&q));
+        assert_eq!(pg.add(&pg).to_jacobian().to_affine(), g.double());
+        assert_eq!(pg.double().to_jacobian().to_affine(), g.double());
+        assert_eq!(pg.add(&infinity).to_jacobian().to_affine(), g);
+        assert_eq!(infinity.add(&pg).to_jacobian().to_affine(), g);
+        assert!(infinity
+            .add(&infinity)
+            .to_jacobian()
+            .to_affine()
+            .is_infinity());
+        assert!(infinity.double().to_jacobian().to_affine().is_infinity());
+        let negated = ProjectivePoint { y: -pg.y, ..pg };
+        assert!(pg.add(&negated).to_jacobian().to_affine().is_infinity());
+    }
+
+    #[test]
+    fn test_ladder_edge_scalars() {
+        let g = JacobianPoint::from_affine(&Point::generator());
+        let mut n_minus_one = crate::CURVE_ORDER;
+        n_minus_one[0] -= 1;
+        assert!(g.scalar_mul(&[0; 4]).to_affine().is_infinity());
+        assert_eq!(g.scalar_mul(&[1, 0, 0, 0]).to_affine(), Point::generator());
+        assert!(g.scalar_mul(&crate::CURVE_ORDER).to_affine().is_infinity());
+        let mut minus_g = Point::generator();
+        minus_g.y = minus_g.y.map(|y| -y);
+        assert_eq!(g.scalar_mul(&n_minus_one).to_affine(), minus_g);
+        assert!(JacobianPoint::infinity()
+            .scalar_mul(&n_minus_one)
+            .to_affine()
+            .is_infinity());
+    }
+}
@@ -1,288 +1,1381 @@
-use std::ops::{Add, Sub, Mul, Div};
-use std::fmt;
+//! secp256k1 elliptic curve arithmetic, ECDSA and BIP340 Schnorr signatures.
+//!
+//! ```
+//! use secp256k1::{Curve, Point, Scalar, CURVE_ORDER};
+//!
+//! // 7·G, through the general and the generator-specific multiplication
+//! let k = Scalar::new([7, 0, 0, 0]);
+//! let p = Point::generator().scalar_mul(&[7, 0, 0, 0]);
+//! assert_eq!(p, Point::mul_generator(&k));
+//! assert!(p.is_on_curve());
+//!
+//! // n·G is the point at infinity
+//! assert!(Point::generator().scalar_mul(&CURVE_ORDER).is_infinity());
+//!
+//! // The domain parameters, as published in SEC 2
+//! let curve = Curve::secp256k1();
+//! assert!(curve.generator().is_on_curve());
+//! let n = [0xBFD25E8CD0364141, 0xBAAEDCE6AF48A03B, 0xFFFFFFFFFFFFFFFE, 0xFFFFFFFFFFFFFFFF];
+//! assert_eq!(curve.order(), n);
+//! ```
 
-// Field modulus for secp256k1
-const FIELD_MODULUS: [u64; 4] = [
+// Without the default std feature the crate only needs core and alloc. The
+// cached generator tables need std, so generator multiplication falls back
+// to the general scalar multiplications. Without the default hashes feature
+// there is no SHA-256, and with it go everything built on a hash: RFC 6979
+// nonces, Schnorr, ECDH, WIF and addresses, hash-to-curve and the vartime
+// generator table. The field and curve arithmetic, the comb, Pedersen
+// commitments and plain ECDSA remain.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::{Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Neg};
+use core::fmt;
+
+use rand_core::{CryptoRng, RngCore};
+
+pub mod base58;
+#[cfg(feature = "std")]
+mod comb;
+mod curve;
+#[cfg(feature = "hashes")]
+mod ecdh;
+mod ecdsa;
+#[cfg(any(fuzz, test))]
+pub mod fuzz;
+mod glv;
+#[cfg(feature = "hashes")]
+mod hash_to_curve;
+#[cfg(feature = "hashes")]
+pub mod hashes;
+mod hex;
+mod keys;
+mod ladder;
+mod msm;
+mod pedersen;
+#[cfg(feature = "hashes")]
+mod rfc6979;
+#[cfg(feature = "hashes")]
+mod schnorr;
+#[cfg(test)]
+mod test_vectors;
+#[cfg(feature = "serde")]
+mod serde_impl;
+#[cfg(all(feature = "std", feature = "hashes"))]
+mod table;
+
+pub use curve::Curve;
+#[cfg(feature = "hashes")]
+pub use ecdh::ecdh;
+pub use ecdsa::{
+    recover, sign_ecdsa, sign_ecdsa_recoverable, verify_batch, verify_ecdsa, verify_ecdsa_low_s,
+    RecoverableSignature, Signature,
+};
+#[cfg(feature = "hashes")]
+pub use ecdsa::{sign_ecdsa_deterministic, sign_ecdsa_recoverable_deterministic};
+#[cfg(feature = "hashes")]
+pub use hash_to_curve::hash_to_curve;
+#[cfg(feature = "hashes")]
+pub use keys::aggregate_keys;
+pub use keys::{public_key, Keypair, Parity, PrivateKey, PublicKey, XOnlyPublicKey};
+pub use msm::multi_scalar_mul_vartime;
+pub use pedersen::{pedersen_commit, PEDERSEN_H};
+#[cfg(feature = "hashes")]
+pub use rfc6979::rfc6979_nonce;
+#[cfg(feature = "hashes")]
+pub use schnorr::{schnorr_sign, schnorr_verify};
+#[cfg(all(feature = "std", feature = "hashes"))]
+pub use table::{load_generator_table, serialize_generator_table};
+
+// Field modulus for secp256k1: p = 2^256 - 2^32 - 977
+// All multi-limb constants are little-endian (value[0] is the least significant limb)
+pub const FIELD_MODULUS: [u64; 4] = [
+    0xFFFFFFFEFFFFFC2F,
     0xFFFFFFFFFFFFFFFF,
-    0xFFFFFFFFFFFFFFFE,
-    0xBAAEDCE6AF48A03B,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+];
+
+// 2^256 mod p, used to fold the high half of a product back into range
+const FIELD_FOLD: [u64; 4] = [0x1000003D1, 0, 0, 0];
+
+// Order of the generator point
+pub const CURVE_ORDER: [u64; 4] = [
     0xBFD25E8CD0364141,
+    0xBAAEDCE6AF48A03B,
+    0xFFFFFFFFFFFFFFFE,
+    0xFFFFFFFFFFFFFFFF,
 ];
 
-// Curve parameters
+// 2^256 mod n
+const ORDER_FOLD: [u64; 4] = [0x402DA1732FC9BEBF, 0x4551231950B75FC4, 0x1, 0];
+
+// Curve parameters: y² = x³ + 7, and the generator G = (GENERATOR_X, GENERATOR_Y)
 const CURVE_A: u64 = 0;
-const CURVE_B: [u64; 4] = [7, 0, 0, 0];
-const GENERATOR_X: [u64; 4] = [
-    0x79BE667EF9DCBBAC,
+pub const CURVE_B: [u64; 4] = [7, 0, 0, 0];
+pub const GENERATOR_X: [u64; 4] = [
+    0x59F2815B16F81798,
+    0x029BFCDB2DCE28D9,
     0x55A06295CE870B07,
-    0x037BF51F5CAEE4B7,
-    0x5CBF3BA3D23861BE,
+    0x79BE667EF9DCBBAC,
 ];
-const GENERATOR_Y: [u64; 4] = [
-    0x483ADA7726A3C465,
-    0x5DA4FBFC0E1108A8,
-    0xFD17B448A6855419,
+pub const GENERATOR_Y: [u64; 4] = [
     0x9C47D08FFB10D4B8,
+    0xFD17B448A6855419,
+    0x5DA4FBFC0E1108A8,
+    0x483ADA7726A3C465,
 ];
 
+// Multi-limb helpers shared by the field and scalar arithmetic
+
+// a + b, returning the sum and the carry out of the top limb
+fn add_limbs(a: &[u64; 4], b: &[u64; 4]) -> ([u64; 4], u64) {
+    let mut result = [0u64; 4];
+    let mut carry = 0u64;
+    for i in 0..4 {
+        let (sum, c1) = a[i].overflowing_add(b[i]);
+        let (sum, c2) = sum.overflowing_add(carry);
+        result[i] = sum;
+        carry = (c1 | c2) as u64;
+    }
+    (result, carry)
+}
+
+// a - b, returning the difference and the borrow out of the top limb
+fn sub_limbs(a: &[u64; 4], b: &[u64; 4]) -> ([u64; 4], u64) {
+    let mut result = [0u64; 4];
+    let mut borrow = 0u64;
+    for i in 0..4 {
+        let (diff, b1) = a[i].overflowing_sub(b[i]);
+        let (diff, b2) = diff.overflowing_sub(borrow);
+        result[i] = diff;
+        borrow = (b1 | b2) as u64;
+    }
+    (result, borrow)
+}
+
+// mask ? a : b, where mask is all ones or all zeros
+fn select_limbs(mask: u64, a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+    let mut result = [0u64; 4];
+    for i in 0..4 {
+        result[i] = (a[i] & mask) | (b[i] & !mask);
+    }
+    result
+}
+
+// All ones for choice 1, all zeros for choice 0; only the low bit is used
+fn choice_mask(choice: u8) -> u64 {
+    ((choice & 1) as u64).wrapping_neg()
+}
+
+// value - modulus if carry is set or value >= modulus, else value. The
+// subtraction is always done and the result picked with a mask, so timing
+// does not depend on which case applies.
+fn sub_if_geq(value: &[u64; 4], carry: u64, modulus: &[u64; 4]) -> [u64; 4] {
+    let (diff, borrow) = sub_limbs(value, modulus);
+    // Keep value only when it is below modulus and nothing carried out
+    let keep = (borrow & !carry & 1).wrapping_neg();
+    select_limbs(keep, value, &diff)
+}
+
+// a == b, folding every limb difference together before the single
+// comparison so the time taken does not depend on where they differ
+fn ct_eq_limbs(a: &[u64; 4], b: &[u64; 4]) -> bool {
+    let mut diff = 0u64;
+    for i in 0..4 {
+        diff |= a[i] ^ b[i];
+    }
+    // Top bit of diff | -diff is set exactly when diff is non-zero
+    ((diff | diff.wrapping_neg()) >> 63) == 0
+}
+
+// a >= b, comparing from the most significant limb. Variable time; only
+// used to validate untrusted input and in other public-data checks.
+fn geq_limbs(a: &[u64; 4], b: &[u64; 4]) -> bool {
+    for i in (0..4).rev() {
+        if a[i] != b[i] {
+            return a[i] > b[i];
+        }
+    }
+    true
+}
+
+// Big-endian bytes to little-endian limbs
+fn limbs_from_bytes(bytes: &[u8; 32]) -> [u64; 4] {
+    let mut limbs = [0u64; 4];
+    for (i, chunk) in bytes.chunks_exact(8).enumerate() {
+        let mut word = [0u8; 8];
+        word.copy_from_slice(chunk);
+        limbs[3 - i] = u64::from_be_bytes(word);
+    }
+    limbs
+}
+
+// Little-endian limbs to big-endian bytes
+fn limbs_to_bytes(limbs: &[u64; 4]) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (i, chunk) in bytes.chunks_exact_mut(8).enumerate() {
+        chunk.copy_from_slice(&limbs[3 - i].to_be_bytes());
+    }
+    bytes
+}
+
+// Full 256x256 -> 512 bit schoolbook multiplication
+fn mul_wide(a: &[u64; 4], b: &[u64; 4]) -> [u64; 8] {
+    let mut result = [0u64; 8];
+    for i in 0..4 {
+        let mut carry: u64 = 0;
+        for j in 0..4 {
+            let t = (a[i] as u128) * (b[j] as u128) + result[i + j] as u128 + carry as u128;
+            result[i + j] = t as u64;
+            carry = (t >> 64) as u64;
+        }
+        result[i + 4] = carry;
+    }
+    result
+}
+
+// Reduce a 512-bit value modulo m = 2^256 - fold by folding the high half
+// back in (hi * 2^256 + lo == hi * fold + lo mod m), then one conditional
+// subtraction of m. For both fold constants (below 2^130) four folds always
+// bring the value under 2^256, which is below 2m; running a fixed number of
+// them, even once the high half is zero, keeps the timing independent of
+// the value.
+fn reduce_wide(wide: [u64; 8], modulus: &[u64; 4], fold: &[u64; 4]) -> [u64; 4] {
+    let mut acc = wide;
+    for _ in 0..4 {
+        let hi = [acc[4], acc[5], acc[6], acc[7]];
+        let mut next = mul_wide(&hi, fold);
+        let mut carry = 0u64;
+        for i in 0..8 {
+            let lo = if i < 4 { acc[i] } else { 0 };
+            let t = next[i] as u128 + lo as u128 + carry as u128;
+            next[i] = t as u64;
+            carry = (t >> 64) as u64;
+        }
+        acc = next;
+    }
+    debug_assert!(acc[4..] == [0u64; 4]);
+
+    sub_if_geq(&[acc[0], acc[1], acc[2], acc[3]], 0, modulus)
+}
+
+// Reduction modulo p specialised to p = 2^256 - 0x1000003D1: the high half
+// is folded in with single-limb multiplies, twice, and one conditional
+// subtraction finishes. Same result as reduce_wide with FIELD_FOLD, several
+// times cheaper, and likewise constant time.
+fn reduce_field_wide(wide: [u64; 8]) -> [u64; 4] {
+    const FOLD: u128 = FIELD_FOLD[0] as u128;
+
+    // lo + hi·FOLD, a 290-bit value held in four limbs plus a small top limb
+    let mut acc = [0u64; 4];
+    let mut carry = 0u128;
+    for i in 0..4 {
+        let t = wide[i] as u128 + wide[i + 4] as u128 * FOLD + carry;
+        acc[i] = t as u64;
+        carry = t >> 64;
+    }
+
+    // Fold the top limb (below 2^35) the same way
+    let mut carry = carry * FOLD;
+    for limb in acc.iter_mut() {
+        let t = *limb as u128 + carry;
+        *limb = t as u64;
+        carry = t >> 64;
+    }
+
+    // A final carry out means the value wrapped past 2^256; what is left is
+    // then tiny, so adding FOLD once more cannot carry again
+    let (acc, _) = add_limbs(&acc, &[(carry as u64).wrapping_neg() & FOLD as u64, 0, 0, 0]);
+    sub_if_geq(&acc, 0, &FIELD_MODULUS)
+}
+
+// Binary extended Euclidean inversion modulo an odd prime, for a nonzero
+// value below it (Guide to Elliptic Curve Cryptography, algorithm 2.22).
+// Much faster than exponentiation but its running time depends on the
+// input, so it is only for public values.
+fn inv_vartime_limbs(value: &[u64; 4], modulus: &[u64; 4]) -> [u64; 4] {
+    const ONE: [u64; 4] = [1, 0, 0, 0];
+
+    // x / 2 mod m for x below m, adding m first when x is odd
+    let halve = |x: &mut [u64; 4]| {
+        let mut carry = 0;
+        if x[0] & 1 == 1 {
+            let (sum, c) = add_limbs(x, modulus);
+            *x = sum;
+            carry = c;
+        }
+        shift_right_one(x, carry);
+    };
+    // x - y mod m for x, y below m
+    let sub_mod = |x: &[u64; 4], y: &[u64; 4]| {
+        let (diff, borrow) = sub_limbs(x, y);
+        if borrow != 0 {
+            add_limbs(&diff, modulus).0
+        } else {
+            diff
+        }
+    };
+
+    let mut u = *value;
+    let mut v = *modulus;
+    let mut x1 = ONE;
+    let mut x2 = [0u64; 4];
+    while u != ONE && v != ONE {
+        while u[0] & 1 == 0 {
+            shift_right_one(&mut u, 0);
+            halve(&mut x1);
+        }
+        while v[0] & 1 == 0 {
+            shift_right_one(&mut v, 0);
+            halve(&mut x2);
+        }
+        if geq_limbs(&u, &v) {
+            u = sub_limbs(&u, &v).0;
+            x1 = sub_mod(&x1, &x2);
+        } else {
+            v = sub_limbs(&v, &u).0;
+            x2 = sub_mod(&x2, &x1);
+        }
+    }
+    if u == ONE {
+        x1
+    } else {
+        x2
+    }
+}
+
+// x >> 1, shifting top_bit in at the most significant end
+fn shift_right_one(x: &mut [u64; 4], top_bit: u64) {
+    for i in 0..3 {
+        x[i] = (x[i] >> 1) | (x[i + 1] << 63);
+    }
+    x[3] = (x[3] >> 1) | (top_bit << 63);
+}
+
+// What Montgomery's trick needs from an element, for both p and n
+trait Invertible: Copy + Mul<Output = Self> {
+    fn one() -> Self;
+    fn is_zero(&self) -> bool;
+    fn inv(&self) -> Self;
+}
+
+impl Invertible for FieldElement {
+    fn one() -> Self {
+        FieldElement::from(1)
+    }
+    fn is_zero(&self) -> bool {
+        FieldElement::is_zero(self)
+    }
+    fn inv(&self) -> Self {
+        FieldElement::inv(self)
+    }
+}
+
+impl Invertible for Scalar {
+    fn one() -> Self {
+        Scalar::from(1)
+    }
+    fn is_zero(&self) -> bool {
+        Scalar::is_zero(self)
+    }
+    fn inv(&self) -> Self {
+        Scalar::inv(self)
+    }
+}
+
+// Invert every element in place with Montgomery's trick: one inversion of
+// the product of all of them plus three multiplications per element. Zeros
+// are skipped and stay zero, matching inv, so they cannot spoil the shared
+// product.
+fn batch_inverse_in_place<T: Invertible>(elems: &mut [T]) {
+    // prefix[i] is the product of the non-zero elements before i
+    let mut prefix = Vec::with_capacity(elems.len());
+    let mut acc = T::one();
+    for elem in elems.iter() {
+        prefix.push(acc);
+        if !elem.is_zero() {
+            acc = acc * *elem;
+        }
+    }
+
+    let mut inv = acc.inv();
+    for (elem, before) in elems.iter_mut().zip(prefix).rev() {
+        if elem.is_zero() {
+            continue;
+        }
+        let next = inv * *elem;
+        *elem = inv * before;
+        inv = next;
+    }
+}
+
 // Field element representation
 #[derive(Clone, Copy, PartialEq, Eq)]
-struct FieldElement {
+pub struct FieldElement {
     value: [u64; 4],
 }
 
 impl FieldElement {
     // Create a new field element from u64 array
-    fn new(value: [u64; 4]) -> Self {
+    pub fn new(value: [u64; 4]) -> Self {
         let mut reduced = value;
         FieldElement::reduce(&mut reduced);
-        FieldElement { value: reduced }
+        let element = FieldElement { value: reduced };
+        debug_assert!(element.is_valid(), "reduction left {:?} >= p", reduced);
+        element
+    }
+
+    // Whether the value is canonical, i.e. below p. Every constructor and
+    // operation maintains this; it exists to check that they do.
+    pub fn is_valid(&self) -> bool {
+        !geq_limbs(&self.value, &FIELD_MODULUS)
     }
 
     // Reduce field element modulo prime
     fn reduce(value: &mut [u64; 4]) {
-        let mut carry: u64 = 0;
-        for i in 0..4 {
-            let temp = value[i].wrapping_add(carry);
-            value[i] = temp;
-            carry = temp >> 63;
-        }
-
-        // Modular reduction using Barrett reduction
-        let mut q = [0u64; 8];
-        let mut r = [0u64; 8];
-
-        // Multiplication approach for modular reduction
-        for i in 0..4 {
-            for j in 0..4 {
-                let (high, low) = Self::wide_mul(value[i], FIELD_MODULUS[j]);
-                q[i + j] = q[i + j].wrapping_add(low);
-                q[i + j + 1] = q[i + j + 1].wrapping_add(high);
-            }
-        }
+        // Any 256-bit value is below 2p, so one conditional subtraction suffices
+        *value = sub_if_geq(value, 0, &FIELD_MODULUS);
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value == [0u64; 4]
+    }
 
-        // Subtract multiples of modulus
-        for i in 0..4 {
-            value[i] = r[i];
+    // b if choice is 1, a if it is 0, selected with a bit mask rather than a
+    // branch so secret choices do not show in the timing
+    pub fn conditional_select(a: &Self, b: &Self, choice: u8) -> Self {
+        FieldElement {
+            value: select_limbs(choice_mask(choice), &b.value, &a.value),
         }
     }
 
-    // Wide multiplication to handle large numbers
-    fn wide_mul(a: u64, b: u64) -> (u64, u64) {
-        let a128 = a as u128;
-        let b128 = b as u128;
-        let product = a128 * b128;
-        ((product >> 64) as u64, product as u64)
+    // Replace self with -self if choice is 1, without branching
+    pub fn conditional_negate(&mut self, choice: u8) {
+        *self = FieldElement::conditional_select(self, &-*self, choice);
     }
 
-    // Multiplicative inverse using extended Euclidean algorithm
-    fn inv(&self) -> Self {
-        // Fermat's little theorem: a^-1 ≡ a^(p-2) (mod p)
-        let mut exp = FIELD_MODULUS;
-        exp[0] -= 2; // p-2
+    // Constant-time equality for secret values; == may stop at the first
+    // differing limb
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        ct_eq_limbs(&self.value, &other.value)
+    }
+
+    // self², through the same fast reduction as Mul
+    pub fn square(&self) -> Self {
+        FieldElement {
+            value: reduce_field_wide(mul_wide(&self.value, &self.value)),
+        }
+    }
 
-        let mut result = FieldElement::new([1, 0, 0, 0]);
-        let mut base = *self;
+    // Modular exponentiation by square-and-multiply. It branches on the bits
+    // of exp but not on self, so with a fixed public exponent it is constant
+    // time in the base.
+    fn pow(&self, exp: &[u64; 4]) -> Self {
+        let mut result = FieldElement::from(1);
 
         for word in exp.iter().rev() {
             for bit in (0..64).rev() {
-                result = result * result;
+                result = result.square();
                 if (word & (1 << bit)) != 0 {
-                    result = result * *self;
+                    result *= *self;
                 }
             }
         }
 
         result
     }
+
+    // Multiplicative inverse, or zero for zero. Constant time: the
+    // exponentiation only branches on the fixed exponent p-2, so it is safe
+    // on secret values.
+    pub fn inv(&self) -> Self {
+        // Fermat's little theorem: a^-1 ≡ a^(p-2) (mod p)
+        let mut exp = FIELD_MODULUS;
+        exp[0] -= 2; // p-2
+        self.pow(&exp)
+    }
+
+    // Same result as inv, several times faster, but the running time depends
+    // on the value; only use it on public data
+    pub fn inv_vartime(&self) -> Self {
+        if self.is_zero() {
+            return *self;
+        }
+        FieldElement {
+            value: inv_vartime_limbs(&self.value, &FIELD_MODULUS),
+        }
+    }
+
+    // Inverse that refuses zero instead of silently returning zero
+    pub fn try_inv(&self) -> Result<Self, Error> {
+        if self.is_zero() {
+            return Err(Error::ZeroScalar);
+        }
+        Ok(self.inv())
+    }
+
+    // Invert every element in place with one shared inversion; see
+    // batch_inverse_in_place. Zeros stay zero, matching inv.
+    pub fn batch_inverse(elems: &mut [FieldElement]) {
+        batch_inverse_in_place(elems);
+    }
+
+    // Square root, if one exists. p ≡ 3 (mod 4), so a candidate is
+    // a^((p+1)/4); it is a root exactly when a is a quadratic residue.
+    pub fn sqrt(&self) -> Option<Self> {
+        const EXP: [u64; 4] = [
+            0xFFFFFFFFBFFFFF0C,
+            0xFFFFFFFFFFFFFFFF,
+            0xFFFFFFFFFFFFFFFF,
+            0x3FFFFFFFFFFFFFFF,
+        ];
+        let root = self.pow(&EXP);
+        if root * root == *self {
+            Some(root)
+        } else {
+            None
+        }
+    }
+
+    pub fn is_odd(&self) -> bool {
+        self.value[0] & 1 == 1
+    }
+
+    // Big-endian 32-byte encoding
+    pub fn to_bytes(&self) -> [u8; 32] {
+        limbs_to_bytes(&self.value)
+    }
+
+    // Parse a 32-byte big-endian field element, rejecting values >= p
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self, Error> {
+        let limbs = limbs_from_bytes(bytes);
+        if geq_limbs(&limbs, &FIELD_MODULUS) {
+            return Err(Error::InvalidEncoding);
+        }
+        Ok(FieldElement { value: limbs })
+    }
+
+    // Parse 64 hex digits (big-endian, either case), rejecting values >= p
+    pub fn from_hex(s: &str) -> Result<Self, Error> {
+        Self::from_bytes(&hex::decode32_checked(s)?)
+    }
+}
+
+// Small integers, e.g. FieldElement::from(7) for the curve's b
+impl From<u64> for FieldElement {
+    fn from(value: u64) -> Self {
+        FieldElement::new([value, 0, 0, 0])
+    }
+}
+
+// Big-endian bytes. Panics if the value is not below p; use
+// FieldElement::from_bytes to handle untrusted input.
+impl From<[u8; 32]> for FieldElement {
+    fn from(bytes: [u8; 32]) -> Self {
+        FieldElement::from_bytes(&bytes).expect("field element is not below p")
+    }
 }
 
 // Arithmetic implementations
 impl Add for FieldElement {
     type Output = Self;
     fn add(self, other: Self) -> Self {
-        let mut result = [0u64; 4];
-        let mut carry: u64 = 0;
-        for i in 0..4 {
-            let temp = self.value[i].wrapping_add(other.value[i]).wrapping_add(carry);
-            result[i] = temp;
-            carry = temp >> 63;
+        let (sum, carry) = add_limbs(&self.value, &other.value);
+        // Both operands are below p, so the sum is below 2p
+        FieldElement {
+            value: sub_if_geq(&sum, carry, &FIELD_MODULUS),
         }
-        FieldElement::new(result)
     }
 }
 
 impl Sub for FieldElement {
     type Output = Self;
     fn sub(self, other: Self) -> Self {
-        let mut result = [0u64; 4];
-        let mut borrow: u64 = 0;
-        for i in 0..4 {
-            let temp = self.value[i].wrapping_sub(other.value[i]).wrapping_sub(borrow);
-            result[i] = temp;
-            borrow = temp >> 63;
+        let (diff, borrow) = sub_limbs(&self.value, &other.value);
+        // Add p back on underflow, selecting with a mask instead of branching
+        let wrapped = add_limbs(&diff, &FIELD_MODULUS).0;
+        FieldElement {
+            value: select_limbs(borrow.wrapping_neg(), &wrapped, &diff),
         }
-        FieldElement::new(result)
+    }
+}
+
+impl Neg for FieldElement {
+    type Output = Self;
+    fn neg(self) -> Self {
+        FieldElement::from(0) - self
     }
 }
 
 impl Mul for FieldElement {
     type Output = Self;
     fn mul(self, other: Self) -> Self {
-        let mut result = [0u64; 8];
-        for i in 0..4 {
-            let mut carry: u64 = 0;
-            for j in 0..4 {
-                let (high, low) = FieldElement::wide_mul(self.value[i], other.value[j]);
-                let temp = result[i + j].wrapping_add(low).wrapping_add(carry);
-                result[i + j] = temp;
-                carry = high.wrapping_add(temp >> 63);
-            }
-            result[i + 4] = carry;
+        let wide = mul_wide(&self.value, &other.value);
+        FieldElement {
+            value: reduce_field_wide(wide),
         }
+    }
+}
 
-        let mut reduced = [0u64; 4];
-        for i in 0..4 {
-            reduced[i] = result[i];
-        }
-        FieldElement::new(reduced)
+impl AddAssign for FieldElement {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
     }
 }
 
-// Point representation on the elliptic curve
-#[derive(Clone, Copy)]
-struct Point {
-    x: Option<FieldElement>,
-    y: Option<FieldElement>,
+impl SubAssign for FieldElement {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
 }
 
-impl Point {
-    // Point addition on the curve
-    fn add(&self, other: &Point) -> Point {
-        match (self.x, self.y, other.x, other.y) {
-            // Point at infinity cases
-            (None, _, _, _) => *other,
-            (_, _, None, _) => *self,
+impl MulAssign for FieldElement {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
 
-            // Special cases where one point has only x coordinate
-            (Some(_), None, Some(_), _) | (Some(_), None, _, _) => *self,
-            (Some(_), _, Some(_), None) | (_, _, Some(_), None) => *other,
+// Scalar modulo the group order n
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Scalar {
+    value: [u64; 4],
+}
 
-            // General point addition for points with full coordinates
-            (Some(x1), Some(y1), Some(x2), Some(y2)) => {
-                // Slope calculation
-                let slope = if x1 == x2 && y1 == y2 {
-                    // Point doubling case
-                    let three_x_squared = FieldElement::new([3, 0, 0, 0]) * x1 * x1;
-                    let two_y = FieldElement::new([2, 0, 0, 0]) * y1;
-                    three_x_squared * two_y.inv()
-                } else {
-                    // Point addition case
-                    (y2 - y1) * (x2 - x1).inv()
-                };
-
-                // New x coordinate
-                let x3 = slope * slope - x1 - x2;
+impl Scalar {
+    // Create a new scalar from u64 array, reducing modulo n
+    pub fn new(value: [u64; 4]) -> Self {
+        // Any 256-bit value is below 2n, so one conditional subtraction suffices
+        Scalar {
+            value: sub_if_geq(&value, 0, &CURVE_ORDER),
+        }
+    }
 
-                // New y coordinate
-                let y3 = slope * (x1 - x3) - y1;
+    // Parse a 32-byte big-endian scalar. Values >= n are ScalarOutOfRange
+    // rather than being reduced; use from_bytes_reduced for hash outputs.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self, Error> {
+        let limbs = limbs_from_bytes(bytes);
+        if geq_limbs(&limbs, &CURVE_ORDER) {
+            return Err(Error::ScalarOutOfRange);
+        }
+        Ok(Scalar { value: limbs })
+    }
 
-                Point {
-                    x: Some(x3),
-                    y: Some(y3),
-                }
-            }
+    // Parse 64 hex digits with the same range check as from_bytes
+    pub fn from_hex(s: &str) -> Result<Self, Error> {
+        Self::from_bytes(&hex::decode32_checked(s)?)
+    }
+
+    // Interpret 32 big-endian bytes as an integer and reduce it modulo n
+    pub fn from_bytes_reduced(bytes: &[u8; 32]) -> Self {
+        Scalar::new(limbs_from_bytes(bytes))
+    }
+
+    // Reduce a 64-byte big-endian integer modulo n. Hashing 512 uniform bits
+    // down this way leaves a bias of about 2^-256, unlike from_bytes_reduced
+    // whose 256-bit input is biased towards the low end by about 2^-128.
+    pub fn from_bytes_wide(bytes: &[u8; 64]) -> Self {
+        let hi = limbs_from_bytes(bytes[..32].try_into().unwrap());
+        let lo = limbs_from_bytes(bytes[32..].try_into().unwrap());
+        let wide = [lo[0], lo[1], lo[2], lo[3], hi[0], hi[1], hi[2], hi[3]];
+        Scalar {
+            value: reduce_wide(wide, &CURVE_ORDER, &ORDER_FOLD),
+        }
+    }
+
+    // Small signed integers; -m maps to n - m
+    pub fn from_i64(value: i64) -> Self {
+        let mut scalar = Scalar::from(value.unsigned_abs());
+        scalar.conditional_negate((value < 0) as u8);
+        scalar
+    }
+
+    // Big-endian 32-byte encoding
+    pub fn to_bytes(&self) -> [u8; 32] {
+        limbs_to_bytes(&self.value)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value == [0u64; 4]
+    }
+
+    // Bit i (0 is the least significant), or 0 for i >= 256. Only the
+    // public index decides which limb is read.
+    pub fn bit(&self, i: usize) -> u8 {
+        if i >= 256 {
+            return 0;
+        }
+        ((self.value[i / 64] >> (i % 64)) & 1) as u8
+    }
+
+    // Position of the highest set bit plus one, 0 for zero. Every limb is
+    // examined and the answer picked with masks, so the cost does not
+    // depend on the value.
+    pub fn bit_length(&self) -> u32 {
+        let mut length = 0u64;
+        for (i, limb) in self.value.iter().enumerate() {
+            let nonzero = ((limb | limb.wrapping_neg()) >> 63).wrapping_neg();
+            let limb_length = 64 * i as u64 + 64 - limb.leading_zeros() as u64;
+            length = (limb_length & nonzero) | (length & !nonzero);
+        }
+        length as u32
+    }
+
+    // Constant-time selection and negation, as for FieldElement
+    pub fn conditional_select(a: &Self, b: &Self, choice: u8) -> Self {
+        Scalar {
+            value: select_limbs(choice_mask(choice), &b.value, &a.value),
         }
     }
 
-    // Scalar multiplication using double-and-add method
-    fn scalar_mul(&self, scalar: &[u64; 4]) -> Point {
-        let mut result = Point { x: None, y: None };
-        let mut base = *self;
+    pub fn conditional_negate(&mut self, choice: u8) {
+        *self = Scalar::conditional_select(self, &-*self, choice);
+    }
+
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        ct_eq_limbs(&self.value, &other.value)
+    }
+
+    // Modular exponentiation by square-and-multiply, branching only on exp
+    fn pow(&self, exp: &[u64; 4]) -> Self {
+        let mut result = Scalar::new([1, 0, 0, 0]);
 
-        for word in scalar.iter().rev() {
+        for word in exp.iter().rev() {
             for bit in (0..64).rev() {
-                result = result.add(&result);
+                result = result * result;
                 if (word & (1 << bit)) != 0 {
-                    result = result.add(&base);
+                    result = result * *self;
                 }
             }
         }
 
         result
     }
-}
 
-// Utility functions for cryptographic operations
-impl Point {
-    // Check if point is on the curve
-    fn is_on_curve(&self) -> bool {
-        match (self.x, self.y) {
-            (Some(x), Some(y)) => {
-                // y² = x³ + 7 (secp256k1 curve equation)
-                let x_cubed = x * x * x;
-                let y_squared = y * y;
-                x_cubed + FieldElement::new(CURVE_B) == y_squared
-            }
-            _ => true // Point at infinity is considered on the curve
+    // Multiplicative inverse modulo n, or zero for zero. Constant time like
+    // FieldElement::inv, so it is suitable for nonces and private keys.
+    pub fn inv(&self) -> Self {
+        // n is prime, so a^-1 ≡ a^(n-2) (mod n)
+        let mut exp = CURVE_ORDER;
+        exp[0] -= 2; // n-2
+        self.pow(&exp)
+    }
+
+    // Variable-time inverse for public values such as signature components
+    pub fn inv_vartime(&self) -> Self {
+        if self.is_zero() {
+            return *self;
+        }
+        Scalar {
+            value: inv_vartime_limbs(&self.value, &CURVE_ORDER),
         }
     }
 
-    // Generator point for secp256k1
-    fn generator() -> Self {
-        Point {
-            x: Some(FieldElement::new(GENERATOR_X)),
-            y: Some(FieldElement::new(GENERATOR_Y)),
+    // Inverse that refuses zero instead of silently returning zero
+    pub fn try_inv(&self) -> Result<Self, Error> {
+        if self.is_zero() {
+            return Err(Error::ZeroScalar);
         }
+        Ok(self.inv())
+    }
+
+    // Invert every scalar in place with one shared inversion, as
+    // FieldElement::batch_inverse does for field elements
+    pub fn batch_inverse(scalars: &mut [Scalar]) {
+        batch_inverse_in_place(scalars);
     }
 }
 
-// Debug implementation for pretty printing
-impl fmt::Debug for FieldElement {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "FieldElement({:?})", self.value)
+// Scalar stays Copy for arithmetic, so it can be wiped explicitly but not
+// on drop; PrivateKey and the RFC 6979 state do that for secrets they own
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Scalar {
+    fn zeroize(&mut self) {
+        self.value.zeroize();
     }
 }
 
-impl fmt::Debug for Point {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match (self.x, self.y) {
-            (Some(x), Some(y)) => write!(f, "Point(x: {:?}, y: {:?})", x, y),
-            _ => write!(f, "Point(Infinity)"),
-        }
+// Small integers, e.g. Scalar::from(2) for doubling
+impl From<u64> for Scalar {
+    fn from(value: u64) -> Self {
+        Scalar::new([value, 0, 0, 0])
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+// Numeric order, which is also the lexicographic order of to_bytes: values
+// are always reduced, so the most significant limb decides first
+impl Ord for Scalar {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.value.iter().rev().cmp(other.value.iter().rev())
+    }
+}
 
-    // Helper function to create a field element from a u64 value
-    fn fe(value: u64) -> FieldElement {
-        FieldElement::new([value, 0, 0, 0])
+impl PartialOrd for Scalar {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
     }
+}
+
+impl Add for Scalar {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        let (sum, carry) = add_limbs(&self.value, &other.value);
+        Scalar {
+            value: sub_if_geq(&sum, carry, &CURVE_ORDER),
+        }
+    }
+}
+
+impl Sub for Scalar {
+    type Output = Self;
+    fn sub(self, other: Self) -> Self {
+        // Add n back on underflow, as FieldElement does with p
+        let (diff, borrow) = sub_limbs(&self.value, &other.value);
+        let wrapped = add_limbs(&diff, &CURVE_ORDER).0;
+        Scalar {
+            value: select_limbs(borrow.wrapping_neg(), &wrapped, &diff),
+        }
+    }
+}
+
+impl Mul for Scalar {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        let wide = mul_wide(&self.value, &other.value);
+        Scalar {
+            value: reduce_wide(wide, &CURVE_ORDER, &ORDER_FOLD),
+        }
+    }
+}
+
+impl Neg for Scalar {
+    type Output = Self;
+    fn neg(self) -> Self {
+        // n - 0 would be n itself, so zero is mapped back to zero with a mask
+        let negated = sub_limbs(&CURVE_ORDER, &self.value).0;
+        let limbs = self.value[0] | self.value[1] | self.value[2] | self.value[3];
+        let nonzero = ((limbs | limbs.wrapping_neg()) >> 63).wrapping_neg();
+        Scalar {
+            value: select_limbs(nonzero, &negated, &[0u64; 4]),
+        }
+    }
+}
+
+// Errors returned by fallible operations
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    // A scalar that must be non-zero (a secret key, a nonce, a value being
+    // inverted) is zero; FieldElement::try_inv reports a zero element too
+    ZeroScalar,
+    // The secret scalar is one, so the public key is the generator itself
+    WeakKey,
+    // The nonce produced r = 0 or s = 0; signing must be retried with another nonce
+    InvalidNonce,
+    // Input bytes have the wrong length or are not a canonical encoding
+    InvalidEncoding,
+    // An embedded checksum does not match the data
+    InvalidChecksum,
+    // Coordinates do not satisfy the curve equation
+    NotOnCurve,
+    // A value that must be a scalar is not below the group order n
+    ScalarOutOfRange,
+    // A computation produced the point at infinity where a real point is required
+    PointAtInfinity,
+    // A signature component is zero, so the signature can never verify
+    InvalidSignature,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Error::ZeroScalar => "scalar is zero",
+            Error::WeakKey => "secret key is one",
+            Error::InvalidNonce => "nonce produced a zero signature component",
+            Error::InvalidEncoding => "invalid encoding",
+            Error::InvalidChecksum => "checksum mismatch",
+            Error::NotOnCurve => "point is not on the curve",
+            Error::ScalarOutOfRange => "scalar is not below the group order",
+            Error::PointAtInfinity => "result is the point at infinity",
+            Error::InvalidSignature => "invalid signature",
+        };
+        f.write_str(message)
+    }
+}
+
+// core::error::Error is std::error::Error, so this also covers std builds
+impl core::error::Error for Error {}
+
+// Point representation on the elliptic curve
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Point {
+    x: Option<FieldElement>,
+    y: Option<FieldElement>,
+}
+
+impl Point {
+    // The point at infinity, the identity of the group
+    pub fn infinity() -> Point {
+        Point { x: None, y: None }
+    }
+
+    // Infinity is the point without an x coordinate
+    pub fn is_infinity(&self) -> bool {
+        self.x.is_none()
+    }
+
+    // Point addition on the curve
+    pub fn add(&self, other: &Point) -> Point {
+        match (self.x, self.y, other.x, other.y) {
+            // Point at infinity cases, i.e. is_infinity() on either side
+            (None, _, _, _) => *other,
+            (_, _, None, _) => *self,
+
+            // Special cases where one point has only x coordinate
+            (Some(_), None, _, _) => *self,
+            (_, _, Some(_), None) => *other,
+
+            // General point addition for points with full coordinates
+            (Some(x1), Some(y1), Some(x2), Some(y2)) => {
+                if x1 == x2 {
+                    // P + P is a doubling; P + (-P) is the point at infinity
+                    return if y1 == y2 { self.double() } else { Point::infinity() };
+                }
+
+                // Chord slope through the two points
+                let slope = (y2 - y1) * (x2 - x1).inv();
+                let x3 = slope * slope - x1 - x2;
+                let y3 = slope * (x1 - x3) - y1;
+                Point {
+                    x: Some(x3),
+                    y: Some(y3),
+                }
+            }
+        }
+    }
+
+    // 2·P with the tangent slope λ = 3x² / 2y. A point with y = 0 is its own
+    // negation, so doubling it (like doubling infinity) gives infinity.
+    pub fn double(&self) -> Point {
+        let (x, y) = match (self.x, self.y) {
+            (Some(x), Some(y)) if !y.is_zero() => (x, y),
+            (Some(_), None) => return *self,
+            _ => return Point::infinity(),
+        };
+        let slope = FieldElement::from(3) * x.square() * (y + y).inv();
+        let x3 = slope.square() - x - x;
+        let y3 = slope * (x - x3) - y;
+        Point {
+            x: Some(x3),
+            y: Some(y3),
+        }
+    }
+
+    // k·P in constant time, by the Montgomery ladder of
+    // JacobianPoint::scalar_mul; the one branch is on P being infinity. This
+    // is the multiplication for secret scalars: ECDH, key tweaks, and the
+    // k * P operators. For public k, scalar_mul_vartime is several times
+    // faster.
+    pub fn scalar_mul(&self, scalar: &[u64; 4]) -> Point {
+        if self.is_infinity() {
+            return Point::infinity();
+        }
+        JacobianPoint::from_affine(self).scalar_mul(scalar).to_affine()
+    }
+
+    // k·P with every intermediate value randomized. Before multiplying, P is
+    // moved to the projective representative (λ²x, λ³y, λ) for a fresh
+    // random λ ≠ 0. That is the same point, and the result is the same k·P,
+    // but every Jacobian coordinate the loop handles carries a factor of a
+    // power of λ, so differs from call to call. This defends against power
+    // and EM analysis that correlates those values with guesses at k. The
+    // sequence of operations is that of scalar_mul, so constant time too.
+    pub fn scalar_mul_blinded<R: CryptoRng + RngCore>(&self, scalar: &Scalar, rng: &mut R) -> Point {
+        if self.is_infinity() {
+            return Point::infinity();
+        }
+        self.scalar_mul_blinded_jacobian(scalar, rng).to_affine()
+    }
+
+    fn scalar_mul_blinded_jacobian<R: CryptoRng + RngCore>(&self, scalar: &Scalar, rng: &mut R) -> JacobianPoint {
+        let lambda = loop {
+            let mut bytes = [0u8; 32];
+            rng.fill_bytes(&mut bytes);
+            match FieldElement::from_bytes(&bytes) {
+                Ok(lambda) if !lambda.is_zero() => break lambda,
+                _ => continue,
+            }
+        };
+        JacobianPoint::from_affine(self).rescale(&lambda).scalar_mul(&scalar.value)
+    }
+}
+
+// Point in Jacobian coordinates: (X, Y, Z) represents the affine point
+// (X/Z², Y/Z³), and Z = 0 represents the point at infinity. Additions and
+// doublings need no field inversion; converting back to affine costs one.
+// Point is the everyday API; this is for callers chaining many operations,
+// who can stay in Jacobian coordinates and convert once at the end, or many
+// points at once with normalize_all.
+#[derive(Clone, Copy)]
+pub struct JacobianPoint {
+    x: FieldElement,
+    y: FieldElement,
+    z: FieldElement,
+}
+
+impl JacobianPoint {
+    // The point at infinity, Z = 0
+    pub fn infinity() -> Self {
+        JacobianPoint {
+            x: FieldElement::from(1),
+            y: FieldElement::from(1),
+            z: FieldElement::from(0),
+        }
+    }
+
+    // Lift an affine point with Z = 1; the point at infinity maps to Z = 0
+    pub fn from_affine(p: &Point) -> Self {
+        match (p.x, p.y) {
+            (Some(x), Some(y)) => JacobianPoint {
+                x,
+                y,
+                z: FieldElement::from(1),
+            },
+            _ => JacobianPoint::infinity(),
+        }
+    }
+
+    // Convert back to affine with a single inversion; Z = 0 maps to infinity
+    pub fn to_affine(&self) -> Point {
+        if self.z.is_zero() {
+            return Point::infinity();
+        }
+        let z_inv = self.z.inv();
+        let z_inv2 = z_inv * z_inv;
+        Point {
+            x: Some(self.x * z_inv2),
+            y: Some(self.y * z_inv2 * z_inv),
+        }
+    }
+
+    // Rescale every point to Z = 1 (infinity keeps Z = 0), sharing one
+    // inversion across the slice through FieldElement::batch_inverse. X and Y
+    // are then the affine coordinates, as to_affine would give them.
+    pub fn normalize_all(points: &mut [JacobianPoint]) {
+        let mut z_inverses: Vec<FieldElement> = points.iter().map(|p| p.z).collect();
+        FieldElement::batch_inverse(&mut z_inverses);
+        for (point, z_inv) in points.iter_mut().zip(z_inverses) {
+            if point.z.is_zero() {
+                continue;
+            }
+            let z_inv2 = z_inv.square();
+            point.x *= z_inv2;
+            point.y *= z_inv2 * z_inv;
+            point.z = FieldElement::from(1);
+        }
+    }
+
+    // The same point as (λ²X, λ³Y, λZ)
+    fn rescale(&self, lambda: &FieldElement) -> Self {
+        let lambda2 = lambda.square();
+        JacobianPoint {
+            x: self.x * lambda2,
+            y: self.y * lambda2 * *lambda,
+            z: self.z * *lambda,
+        }
+    }
+
+    // Compare against an affine point without normalizing:
+    // X == x·Z² and Y == y·Z³
+    #[cfg_attr(not(all(feature = "std", feature = "hashes")), allow(dead_code))]
+    fn eq_affine(&self, p: &Point) -> bool {
+        match (p.x, p.y) {
+            (Some(x), Some(y)) if !self.z.is_zero() => {
+                let z2 = self.z * self.z;
+                self.x == x * z2 && self.y == y * z2 * self.z
+            }
+            (Some(_), Some(_)) => false,
+            _ => self.z.is_zero(),
+        }
+    }
+
+    // Point doubling (dbl-2009-l, a = 0)
+    pub fn double(&self) -> Self {
+        if self.z.is_zero() || self.y.is_zero() {
+            return JacobianPoint::infinity();
+        }
+        let a = self.x * self.x;
+        let b = self.y * self.y;
+        let c = b * b;
+        let xb = self.x + b;
+        let half_d = xb * xb - a - c;
+        let d = half_d + half_d;
+        let e = a + a + a;
+        let f = e * e;
+        let x3 = f - d - d;
+        let two_c = c + c;
+        let four_c = two_c + two_c;
+        let y3 = e * (d - x3) - (four_c + four_c);
+        let yz = self.y * self.z;
+        JacobianPoint {
+            x: x3,
+            y: y3,
+            z: yz + yz,
+        }
+    }
+
+    // Point addition (add-2007-bl), falling back to doubling for P + P
+    pub fn add(&self, other: &JacobianPoint) -> Self {
+        if self.z.is_zero() {
+            return *other;
+        }
+        if other.z.is_zero() {
+            return *self;
+        }
+
+        let z1z1 = self.z * self.z;
+        let z2z2 = other.z * other.z;
+        let u1 = self.x * z2z2;
+        let u2 = other.x * z1z1;
+        let s1 = self.y * other.z * z2z2;
+        let s2 = other.y * self.z * z1z1;
+        let h = u2 - u1;
+        let r = s2 - s1;
+
+        if h.is_zero() {
+            // Same x: either the same point or its negation
+            return if r.is_zero() {
+                self.double()
+            } else {
+                JacobianPoint::infinity()
+            };
+        }
+
+        let hh = h * h;
+        let hhh = h * hh;
+        let v = u1 * hh;
+        let x3 = r * r - hhh - v - v;
+        let y3 = r * (v - x3) - s1 * hhh;
+        let z3 = self.z * other.z * h;
+        JacobianPoint { x: x3, y: y3, z: z3 }
+    }
+}
+
+// Utility functions for cryptographic operations
+impl Point {
+    // Check if point is on the curve
+    pub fn is_on_curve(&self) -> bool {
+        match (self.x, self.y) {
+            (Some(x), Some(y)) => {
+                // y² = x³ + ax + b, with a = 0 and b = 7 for secp256k1
+                let x_cubed = x * x * x;
+                let ax = FieldElement::new([CURVE_A, 0, 0, 0]) * x;
+                let y_squared = y * y;
+                x_cubed + ax + FieldElement::new(CURVE_B) == y_squared
+            }
+            _ => true // Point at infinity is considered on the curve
+        }
+    }
+
+    // The point with the given x coordinate and y parity, if x is on the curve
+    fn from_x(x: FieldElement, odd: bool) -> Option<Point> {
+        let y_squared = x * x * x + FieldElement::new(CURVE_B);
+        let y = y_squared.sqrt()?;
+        let y = if y.is_odd() == odd { y } else { -y };
+        Some(Point { x: Some(x), y: Some(y) })
+    }
+
+    // SEC1 encoding: 0x02/0x03 || x when compressed, 0x04 || x || y otherwise;
+    // the point at infinity is the single byte 0x00
+    pub fn serialize(&self, compressed: bool) -> Vec<u8> {
+        let (x, y) = match (self.x, self.y) {
+            (Some(x), Some(y)) => (x, y),
+            _ => return vec![0x00],
+        };
+        let mut bytes = Vec::with_capacity(65);
+        if compressed {
+            bytes.push(if y.is_odd() { 0x03 } else { 0x02 });
+            bytes.extend_from_slice(&x.to_bytes());
+        } else {
+            bytes.push(0x04);
+            bytes.extend_from_slice(&x.to_bytes());
+            bytes.extend_from_slice(&y.to_bytes());
+        }
+        bytes
+    }
+
+    // The point (x, y), or NotOnCurve if it does not satisfy y² = x³ + 7
+    pub fn from_affine(x: FieldElement, y: FieldElement) -> Result<Point, Error> {
+        let point = Point { x: Some(x), y: Some(y) };
+        if point.is_on_curve() {
+            Ok(point)
+        } else {
+            Err(Error::NotOnCurve)
+        }
+    }
+
+    // Parse a compressed or uncompressed SEC1 encoding of a point on the
+    // curve. Safe on untrusted bytes of any length: a bad length or prefix,
+    // or a coordinate >= p, is InvalidEncoding and a point off the curve is
+    // NotOnCurve. It never panics, and an Ok point is always finite and on
+    // the curve; fuzz::point_from_slice checks this.
+    pub fn from_sec1(bytes: &[u8]) -> Result<Point, Error> {
+        match (bytes.first(), bytes.len()) {
+            (Some(&prefix @ (0x02 | 0x03)), 33) => {
+                let x = FieldElement::from_bytes(bytes[1..33].try_into().unwrap())?;
+                Point::from_x(x, prefix == 0x03).ok_or(Error::NotOnCurve)
+            }
+            (Some(0x04), 65) => {
+                let x = FieldElement::from_bytes(bytes[1..33].try_into().unwrap())?;
+                let y = FieldElement::from_bytes(bytes[33..65].try_into().unwrap())?;
+                Point::from_affine(x, y)
+            }
+            _ => Err(Error::InvalidEncoding),
+        }
+    }
+
+    // Affine coordinates, or None for the point at infinity
+    pub fn x(&self) -> Option<FieldElement> {
+        self.x
+    }
+
+    pub fn y(&self) -> Option<FieldElement> {
+        self.y
+    }
+
+    // Generator point for secp256k1
+    pub fn generator() -> Self {
+        Point {
+            x: Some(FieldElement::new(GENERATOR_X)),
+            y: Some(FieldElement::new(GENERATOR_Y)),
+        }
+    }
+
+    // k·G in constant time. Without std there is no comb table, so this is
+    // the ladder of scalar_mul.
+    #[cfg(not(feature = "std"))]
+    pub fn mul_generator(scalar: &Scalar) -> Point {
+        Point::generator().scalar_mul(&scalar.value)
+    }
+
+    // k·G for public k. Without std or hashes there is no generator table, so
+    // this is scalar_mul_vartime.
+    #[cfg(not(all(feature = "std", feature = "hashes")))]
+    pub fn mul_generator_vartime(scalar: &Scalar) -> Point {
+        Point::generator().scalar_mul_vartime(scalar)
+    }
+}
+
+// Operator forms: p + q is p.add(&q), and k * p and p * k are
+// p.scalar_mul(k), the constant-time multiply, so the short form is safe
+// with a secret k. Call scalar_mul_vartime explicitly when k is public.
+// Point + &Point is implemented too, since method-call syntax picks the
+// by-value trait method over the inherent one and p.add(&q) must keep working.
+impl Add for Point {
+    type Output = Point;
+    fn add(self, other: Point) -> Point {
+        Point::add(&self, &other)
+    }
+}
+
+impl Add<&Point> for Point {
+    type Output = Point;
+    fn add(self, other: &Point) -> Point {
+        Point::add(&self, other)
+    }
+}
+
+impl Mul<Scalar> for Point {
+    type Output = Point;
+    fn mul(self, scalar: Scalar) -> Point {
+        self.scalar_mul(&scalar.value)
+    }
+}
+
+impl Mul<Point> for Scalar {
+    type Output = Point;
+    fn mul(self, point: Point) -> Point {
+        point * self
+    }
+}
+
+// Debug implementation for pretty printing
+impl fmt::Debug for FieldElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FieldElement({:?})", self.value)
+    }
+}
+
+impl fmt::Debug for Scalar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Scalar({:?})", self.value)
+    }
+}
+
+impl fmt::Debug for JacobianPoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "JacobianPoint(x: {:?}, y: {:?}, z: {:?})", self.x, self.y, self.z)
+    }
+}
+
+impl fmt::Debug for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.x, self.y) {
+            (Some(x), Some(y)) => write!(f, "Point(x: {:?}, y: {:?})", x, y),
+            _ => write!(f, "Point(Infinity)"),
+        }
+    }
+}
+
+// Compressed SEC1 hex, the way public keys are usually written
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_infinity() {
+            return write!(f, "Infinity");
+        }
+        for byte in self.serialize(true) {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_vectors::{assert_fe_eq_hex, assert_point_eq_sec1, test_rng};
+    use rand_chacha::rand_core::RngCore;
 
     #[test]
     fn test_field_element_arithmetic() {
-        // Basic addition
-        let a = fe(5);
-        let b = fe(3);
-        let sum = a + b;
-        assert_eq!(sum.value[0], 8);
+        let a = FieldElement::from(5);
+        let b = FieldElement::from(3);
+        assert_fe_eq_hex(a + b, "0000000000000000000000000000000000000000000000000000000000000008");
+        assert_fe_eq_hex(a - b, "0000000000000000000000000000000000000000000000000000000000000002");
+        assert_fe_eq_hex(a * b, "000000000000000000000000000000000000000000000000000000000000000f");
 
-        // Subtraction
-        let diff = a - b;
-        assert_eq!(diff.value[0], 2);
+        // Results that wrap around p
+        let p_minus_one = "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2e";
+        assert_fe_eq_hex(b - a, "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2d");
+        assert_fe_eq_hex(-FieldElement::from(1), p_minus_one);
+        assert_fe_eq_hex(-FieldElement::from(1) + b, "0000000000000000000000000000000000000000000000000000000000000002");
+        assert_fe_eq_hex(-FieldElement::from(1) * -FieldElement::from(1), "0000000000000000000000000000000000000000000000000000000000000001");
 
-        // Multiplication
-        let product = a * b;
-        assert_eq!(product.value[0], 15);
+        // Full-width operands, reference values from Python integers
+        let x = FieldElement::new(GENERATOR_X);
+        let y = FieldElement::new(GENERATOR_Y);
+        assert_fe_eq_hex(x + y, "c1f940f620808011b3455e91dc9813afffb3b123d4537cf2f63a51eb1208ec50");
+        assert_fe_eq_hex(x - y, "31838c07d338f746f7fb6699c076025e058448928748d4bfbdaab0cb1be742e0");
+        assert_fe_eq_hex(y - x, "ce7c73f82cc708b9080499663f89fda1fa7bb76d78b72b4042554f33e418b94f");
+        assert_fe_eq_hex(x * y, "fd3dc529c6eb60fb9d166034cf3c1a5a72324aa9dfd3428a56d7e1ce0179fd9b");
     }
 
     #[test]
     fn test_point_addition() {
         // Test point at infinity cases
-        let infinity = Point { x: None, y: None };
+        let infinity = Point::infinity();
         let generator = Point::generator();
 
         // Adding point to infinity should return the point
@@ -299,67 +1392,233 @@ mod tests {
 
     #[test]
     fn test_point_on_curve() {
-        // Verify generator point is on the curve
-        let generator = Point::generator();
-        assert!(generator.is_on_curve(), "Generator point must be on curve");
-
-        // Test a few manually constructed points
-        let test_x = FieldElement::new([
-            0x79BE667EF9DCBBAC,
-            0x55A06295CE870B07,
-            0x037BF51F5CAEE4B7,
-            0x5CBF3BA3D23861BE,
-        ]);
-        let test_y = FieldElement::new([
-            0x483ADA7726A3C465,
-            0x5DA4FBFC0E1108A8,
-            0xFD17B448A6855419,
-            0x9C47D08FFB10D4B8,
-        ]);
-
-        let test_point = Point {
-            x: Some(test_x),
-            y: Some(test_y),
-        };
-        assert!(test_point.is_on_curve(), "Test point must be on curve");
+        assert_point_eq_sec1(
+            Point::generator(),
+            "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+             483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+        );
+
+        // Points built from known coordinates, on the curve or not
+        let x = FieldElement::from_hex("c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5").unwrap();
+        let y = FieldElement::from_hex("1ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a").unwrap();
+        let two_g = Point { x: Some(x), y: Some(y) };
+        assert!(two_g.is_on_curve());
+        assert_point_eq_sec1(two_g, "02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5");
+        let off_curve = Point { x: Some(x), y: Some(y + FieldElement::from(1)) };
+        assert!(!off_curve.is_on_curve());
+        assert_point_eq_sec1(Point::infinity(), "00");
     }
 
     #[test]
     fn test_scalar_multiplication() {
         let generator = Point::generator();
-        
-        // Test scalar multiplication with small scalar
-        let small_scalar = [2u64, 0, 0, 0];
-        let doubled_point = generator.scalar_mul(&small_scalar);
-        
-        // Verify that scalar multiplication results in a point on the curve
-        assert!(doubled_point.is_on_curve(), "Scalar multiplication must result in point on curve");
-        
-        // Verify that scalar multiplication of 2 is equivalent to point doubling
-        let direct_double = generator.add(&generator);
-        assert_eq!(
-            doubled_point.x.unwrap().value, 
-            direct_double.x.unwrap().value, 
-            "Scalar multiplication by 2 must match point doubling"
+        let mut n_minus_one = CURVE_ORDER;
+        n_minus_one[0] -= 1;
+        let vectors = [
+            ([2, 0, 0, 0], "02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5"),
+            ([3, 0, 0, 0], "02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9"),
+            ([7, 0, 0, 0], "025cbdf0646e5db4eaa398f365f2ea7a0e3d419b7e0330e39ce92bddedcac4f9bc"),
+            (n_minus_one, "0379be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"),
+            (
+                [0x0123456789ABCDEF, 0xFEDCBA9876543210, 0xDEADBEEFCAFEBABE, 0x7FFFFFFFFFFFFFFF],
+                "0236b022fcf169bcad03bc35ed919a0b8eb72710bf96208671b8f621a7381554a7",
+            ),
+            (CURVE_ORDER, "00"),
+        ];
+        for (k, expected) in vectors {
+            assert_point_eq_sec1(generator.scalar_mul(&k), expected);
+        }
+
+        // 2·G agrees with doubling, in both encodings
+        assert_point_eq_sec1(
+            generator.add(&generator),
+            "04c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5\
+             1ae168fea63dc339a3c58419466ceaeef7f632653266d0e1236431a950cfe52a",
         );
     }
 
     #[test]
     fn test_multiplicative_inverse() {
         // Test multiplicative inverse property
-        let a = fe(5);
+        let a = FieldElement::from(5);
         let inv_a = a.inv();
         
         // Verify a * a^-1 = 1 (in field arithmetic)
         let product = a * inv_a;
-        assert_eq!(product.value[0], 1, "Multiplicative inverse must satisfy a * a^-1 = 1");
+        assert_fe_eq_hex(product, "0000000000000000000000000000000000000000000000000000000000000001");
+        // 5^-1 = (3p + 1) / 5
+        assert_fe_eq_hex(inv_a, "99999999999999999999999999999999999999999999999999999998fffffdb6");
+    }
+
+    #[test]
+    fn test_conditional_select_and_negate() {
+        let a = FieldElement::from(3);
+        let b = FieldElement::new(GENERATOR_X);
+        assert_eq!(FieldElement::conditional_select(&a, &b, 0), a);
+        assert_eq!(FieldElement::conditional_select(&a, &b, 1), b);
+        // Only the low bit of choice counts
+        assert_eq!(FieldElement::conditional_select(&a, &b, 3), b);
+        assert_eq!(FieldElement::conditional_select(&a, &b, 2), a);
+
+        let mut c = b;
+        c.conditional_negate(0);
+        assert_eq!(c, b);
+        c.conditional_negate(1);
+        assert_eq!(c, -b);
+        let mut zero = FieldElement::from(0);
+        zero.conditional_negate(1);
+        assert!(zero.is_zero());
+
+        let k = Scalar::from(5);
+        let l = Scalar::new(GENERATOR_Y);
+        assert_eq!(Scalar::conditional_select(&k, &l, 0), k);
+        assert_eq!(Scalar::conditional_select(&k, &l, 1), l);
+        let mut m = l;
+        m.conditional_negate(0);
+        assert_eq!(m, l);
+        m.conditional_negate(1);
+        assert_eq!(m, -l);
+        let mut zero = Scalar::from(0);
+        zero.conditional_negate(1);
+        assert!(zero.is_zero());
+    }
+
+    #[test]
+    fn test_ct_eq_agrees_with_eq() {
+        let field = [
+            FieldElement::from(0),
+            FieldElement::from(1),
+            -FieldElement::from(1),
+            FieldElement::new(GENERATOR_X),
+            FieldElement::new([GENERATOR_X[0], GENERATOR_X[1], GENERATOR_X[2], 0]),
+            FieldElement::new([1 << 63, 0, 0, 0]),
+        ];
+        for a in &field {
+            for b in &field {
+                assert_eq!(a.ct_eq(b), a == b, "{:?} {:?}", a, b);
+            }
+        }
+
+        let scalars = [
+            Scalar::from(0),
+            Scalar::from(1),
+            -Scalar::from(1),
+            Scalar::new(GENERATOR_Y),
+            Scalar::new([0, 0, 0, 1]),
+        ];
+        for a in &scalars {
+            for b in &scalars {
+                assert_eq!(a.ct_eq(b), a == b, "{:?} {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inv_matches_vartime() {
+        let mut p_minus_one = FIELD_MODULUS;
+        p_minus_one[0] -= 1;
+        let mut n_minus_one = CURVE_ORDER;
+        n_minus_one[0] -= 1;
+        let mut limbs = vec![[0, 0, 0, 0], [1, 0, 0, 0], [2, 0, 0, 0], p_minus_one, n_minus_one, GENERATOR_X];
+        let mut rng = test_rng(0x853C_49E6);
+        for _ in 0..32 {
+            limbs.push([rng.next_u64(), rng.next_u64(), rng.next_u64(), rng.next_u64()]);
+        }
+
+        for value in limbs {
+            let a = FieldElement::new(value);
+            assert_eq!(a.inv(), a.inv_vartime());
+            if !a.is_zero() {
+                assert_eq!(a * a.inv_vartime(), FieldElement::from(1));
+            }
+            let k = Scalar::new(value);
+            assert_eq!(k.inv(), k.inv_vartime());
+            if !k.is_zero() {
+                assert_eq!(k * k.inv_vartime(), Scalar::from(1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_fast_field_reduction_matches_generic() {
+        let mut inputs = vec![[u64::MAX; 8], [0u64; 8]];
+        // (p - 1)², the largest product of two field elements
+        let mut p_minus_one = FIELD_MODULUS;
+        p_minus_one[0] -= 1;
+        inputs.push(mul_wide(&p_minus_one, &p_minus_one));
+        // Values that land exactly on p and just past 2^256 after folding
+        inputs.push([FIELD_MODULUS[0], FIELD_MODULUS[1], FIELD_MODULUS[2], FIELD_MODULUS[3], 0, 0, 0, 0]);
+        inputs.push([u64::MAX, u64::MAX, u64::MAX, u64::MAX, 1, 0, 0, 0]);
+        let mut rng = test_rng(0xDA94_2042);
+        for _ in 0..1000 {
+            let mut wide = [0u64; 8];
+            for limb in wide.iter_mut() {
+                *limb = rng.next_u64();
+            }
+            inputs.push(wide);
+        }
+
+        for wide in inputs {
+            assert_eq!(reduce_field_wide(wide), reduce_wide(wide, &FIELD_MODULUS, &FIELD_FOLD), "{:x?}", wide);
+        }
+
+        let x = FieldElement::new(GENERATOR_X);
+        assert_eq!(x.square(), x * x);
+    }
+
+    #[test]
+    fn test_batch_inverse() {
+        let mut elems = vec![
+            FieldElement::from(1),
+            FieldElement::from(2),
+            FieldElement::from(0),
+            -FieldElement::from(1),
+            FieldElement::new(GENERATOR_X),
+            FieldElement::from(0),
+            FieldElement::new(GENERATOR_Y),
+        ];
+        let expected: Vec<FieldElement> = elems.iter().map(|e| e.inv()).collect();
+        FieldElement::batch_inverse(&mut elems);
+        assert_eq!(elems, expected);
+        assert!(elems[2].is_zero() && elems[5].is_zero());
+
+        let mut empty: [FieldElement; 0] = [];
+        FieldElement::batch_inverse(&mut empty);
+        let mut zeros = [FieldElement::from(0); 3];
+        FieldElement::batch_inverse(&mut zeros);
+        assert!(zeros.iter().all(|e| e.is_zero()));
+
+        // The same code inverts scalars modulo n
+        let mut scalars = vec![
+            Scalar::from(1),
+            Scalar::from(0),
+            -Scalar::from(1),
+            Scalar::new([0xDEADBEEF, 0x12345678, 0x9ABCDEF0, 0x0FEDCBA9]),
+        ];
+        let expected: Vec<Scalar> = scalars.iter().map(|k| k.inv()).collect();
+        Scalar::batch_inverse(&mut scalars);
+        assert_eq!(scalars, expected);
+    }
+
+    #[test]
+    fn test_reduce_wide_worst_case() {
+        // 2^512 - 1 needs every fold reduce_wide performs
+        let wide = [u64::MAX; 8];
+        assert_eq!(
+            reduce_wide(wide, &FIELD_MODULUS, &FIELD_FOLD),
+            [0x000007A2000E90A0, 0x0000000000000001, 0, 0]
+        );
+        assert_eq!(
+            reduce_wide(wide, &CURVE_ORDER, &ORDER_FOLD),
+            [0x896CF21467D7D13F, 0x741496C20E7CF878, 0xE697F5E45BCD07C6, 0x9D671CD581C69BC5]
+        );
     }
 
     #[test]
     fn test_edge_cases() {
         // Test addition with points having partial coordinates
         let partial_x_point = Point { 
-            x: Some(fe(10)), 
+            x: Some(FieldElement::from(10)), 
             y: None 
         };
         let full_point = Point::generator();
@@ -372,11 +1631,604 @@ mod tests {
         assert!(result1.x.is_some(), "Addition with partial coordinates should produce a point");
         assert!(result2.x.is_some(), "Addition with partial coordinates should produce a point");
     }
-}
 
-fn main() {
-    // Basic example usage and testing
-    let gen = Point::generator();
-    println!("Generator Point: {:?}", gen);
-    println!("Is generator on curve: {}", gen.is_on_curve());
-}
\ No newline at end of file
+    #[test]
+    fn test_field_reduction() {
+        // p itself and p + 1 reduce to 0 and 1
+        assert!(FieldElement::new(FIELD_MODULUS).is_zero());
+        let mut p_plus_one = FIELD_MODULUS;
+        p_plus_one[0] += 1;
+        assert_eq!(FieldElement::new(p_plus_one), FieldElement::from(1));
+
+        // Subtraction wraps around the modulus: 0 - 1 == p - 1
+        let mut p_minus_one = FIELD_MODULUS;
+        p_minus_one[0] -= 1;
+        assert_eq!((FieldElement::from(0) - FieldElement::from(1)).value, p_minus_one);
+
+        // (p - 1)^2 == 1
+        let minus_one = FieldElement::new(p_minus_one);
+        assert_eq!(minus_one * minus_one, FieldElement::from(1));
+    }
+
+    #[test]
+    fn test_field_near_modulus() {
+        // 2^256 - 1 is the largest input new accepts and reduces to 2^32 + 976
+        let all_ones = FieldElement::new([u64::MAX; 4]);
+        assert_fe_eq_hex(all_ones, "00000000000000000000000000000000000000000000000000000001000003d0");
+        assert_fe_eq_hex(all_ones * all_ones, "000000000000000000000000000000000000000000000001000007a0000e8900");
+
+        let mut p_minus_one = FIELD_MODULUS;
+        p_minus_one[0] -= 1;
+        let minus_one = FieldElement::new(p_minus_one);
+        assert_fe_eq_hex(minus_one + minus_one, "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2d");
+        assert_fe_eq_hex(minus_one * minus_one, "0000000000000000000000000000000000000000000000000000000000000001");
+        assert_fe_eq_hex(minus_one * (minus_one + minus_one), "0000000000000000000000000000000000000000000000000000000000000002");
+
+        // (p - 1)^2 is the largest product of canonical values: its high half
+        // is almost all ones, so every fold runs with its largest carries
+        let wide = mul_wide(&p_minus_one, &p_minus_one);
+        assert_eq!(wide[4..], [0xFFFFFFFDFFFFF85C, u64::MAX, u64::MAX, u64::MAX]);
+        assert_eq!(reduce_field_wide(wide), [1, 0, 0, 0]);
+        assert_eq!(reduce_wide(wide, &FIELD_MODULUS, &FIELD_FOLD), [1, 0, 0, 0]);
+
+        // p - 2^128 and p - 0x123456789, both with their top limbs all ones
+        let a = FieldElement::from_hex("fffffffffffffffffffffffffffffffefffffffffffffffffffffffefffffc2f").unwrap();
+        let b = FieldElement::from_hex("fffffffffffffffffffffffffffffffffffffffffffffffffffffffddcba94a6").unwrap();
+        assert_fe_eq_hex(a + b, "fffffffffffffffffffffffffffffffefffffffffffffffffffffffddcba94a6");
+        assert_fe_eq_hex(b - a, "00000000000000000000000000000000fffffffffffffffffffffffedcba9877");
+        assert_fe_eq_hex(a * b, "0000000000000000000000012345678900000000000000000000000000000000");
+        assert_fe_eq_hex(a * a, "00000000000000000000000000000000000000000000000000000001000003d1");
+    }
+
+    #[test]
+    fn test_sqrt_and_lift() {
+        // 4 has roots ±2; 7 is not a quadratic residue mod p
+        let root = FieldElement::from(4).sqrt().unwrap();
+        assert!(root == FieldElement::from(2) || root == -FieldElement::from(2));
+        assert!(FieldElement::from(7).sqrt().is_none());
+
+        let generator = Point::generator();
+        let x = generator.x.unwrap();
+        assert_eq!(Point::from_x(x, generator.y.unwrap().is_odd()), Some(generator));
+        let flipped = Point::from_x(x, !generator.y.unwrap().is_odd()).unwrap();
+        assert_eq!(flipped.y, Some(-generator.y.unwrap()));
+    }
+
+    #[test]
+    fn test_point_plus_negation_is_infinity() {
+        let generator = Point::generator();
+        let negated = Point {
+            x: generator.x,
+            y: Some(FieldElement::from(0) - generator.y.unwrap()),
+        };
+        assert!(negated.is_on_curve());
+        assert_eq!(generator.add(&negated), Point::infinity());
+        assert!(generator.add(&negated).is_infinity());
+        assert!(!generator.is_infinity());
+    }
+
+    #[test]
+    fn test_point_double() {
+        let g = Point::generator();
+        assert_eq!(g.double(), g.add(&g));
+        assert_eq!(g.double(), g.scalar_mul(&[2, 0, 0, 0]));
+        assert_eq!(g.double().double(), g.scalar_mul(&[4, 0, 0, 0]));
+        assert!(g.double().is_on_curve());
+        assert!(Point::infinity().double().is_infinity());
+    }
+
+    #[test]
+    fn test_point_display() {
+        assert_eq!(
+            format!("{}", Point::generator()),
+            "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+        );
+        assert_eq!(
+            Point::generator().double().to_string(),
+            "02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5"
+        );
+        assert_eq!(format!("{}", Point::infinity()), "Infinity");
+    }
+
+    #[test]
+    fn test_operators() {
+        let g = Point::generator();
+        assert_eq!(Scalar::from(2u64) * g, g + g);
+        assert_eq!(g * Scalar::from(2u64), g.add(&g));
+        assert_eq!(Scalar::from(7u64) * g, Point::mul_generator(&Scalar::new([7, 0, 0, 0])));
+        assert!((Scalar::from(0u64) * g).is_infinity());
+        assert_eq!(g + Point::infinity(), g);
+
+        // The operator is scalar_mul; the vartime method agrees with it
+        let k = Scalar::new([0x0123456789ABCDEF, 0xFEDCBA9876543210, 0xDEADBEEFCAFEBABE, 0x7FFFFFFFFFFFFFFF]);
+        assert_eq!(k * g, g.scalar_mul(&k.value));
+        assert_eq!(g * k, g.scalar_mul_vartime(&k));
+    }
+
+    #[test]
+    fn test_field_assign_operators() {
+        let p_minus_one = -FieldElement::from(1);
+        let values = [
+            FieldElement::from(0),
+            FieldElement::from(1),
+            p_minus_one,
+            FieldElement::new(GENERATOR_X),
+            FieldElement::new(GENERATOR_Y),
+        ];
+        for a in values {
+            for b in values {
+                let mut sum = a;
+                sum += b;
+                assert_eq!(sum, a + b);
+                let mut diff = a;
+                diff -= b;
+                assert_eq!(diff, a - b);
+                let mut product = a;
+                product *= b;
+                assert_eq!(product, a * b);
+            }
+            // Self-assignment, as in the squaring loops
+            let mut square = a;
+            square *= square;
+            assert_eq!(square, a.square());
+        }
+    }
+
+    #[test]
+    fn test_point_from_affine() {
+        let x = FieldElement::new(GENERATOR_X);
+        let y = FieldElement::new(GENERATOR_Y);
+        assert_eq!(Point::from_affine(x, y), Ok(Point::generator()));
+        assert_eq!(Point::from_affine(x, -y), Ok(Point::generator() * -Scalar::from(1)));
+        assert_eq!(Point::from_affine(x, y + FieldElement::from(1)), Err(Error::NotOnCurve));
+        assert_eq!(Point::from_affine(x + FieldElement::from(1), y), Err(Error::NotOnCurve));
+        assert_eq!(Point::from_affine(FieldElement::from(0), FieldElement::from(0)), Err(Error::NotOnCurve));
+    }
+
+    #[test]
+    fn test_infinity() {
+        let infinity = Point::infinity();
+        assert!(infinity.is_infinity());
+        assert!(infinity.is_on_curve());
+        assert_eq!(infinity.add(&infinity), infinity);
+        assert!(infinity.scalar_mul(&[5, 0, 0, 0]).is_infinity());
+        assert!(Point::generator().scalar_mul(&[0, 0, 0, 0]).is_infinity());
+        assert!(Point::generator().scalar_mul(&CURVE_ORDER).is_infinity());
+        assert_eq!(infinity.serialize(true), vec![0x00]);
+    }
+
+    #[test]
+    fn test_jacobian_affine_round_trip() {
+        let generator = Point::generator();
+        assert_eq!(JacobianPoint::from_affine(&generator).to_affine(), generator);
+
+        let infinity = Point::infinity();
+        let jacobian_infinity = JacobianPoint::from_affine(&infinity);
+        assert!(jacobian_infinity.z.is_zero());
+        assert_eq!(jacobian_infinity.to_affine(), infinity);
+    }
+
+    #[test]
+    fn test_jacobian_non_trivial_z() {
+        // (x·z², y·z³, z) is the same point as (x, y, 1)
+        let generator = Point::generator();
+        let z = FieldElement::from(0x1234_5678_9ABC);
+        let jacobian = JacobianPoint {
+            x: generator.x.unwrap() * z * z,
+            y: generator.y.unwrap() * z * z * z,
+            z,
+        };
+        assert_eq!(jacobian.to_affine(), generator);
+
+        // Jacobian doubling and addition agree with the affine formulas
+        let g = JacobianPoint::from_affine(&generator);
+        let doubled = generator.add(&generator);
+        assert_eq!(jacobian.double().to_affine(), doubled);
+        assert_eq!(jacobian.add(&g).to_affine(), doubled);
+        assert_eq!(jacobian.double().add(&g).to_affine(), doubled.add(&generator));
+    }
+
+    #[test]
+    fn test_scalar_mul_blinded() {
+        let mut rng = test_rng(0x510E_527F);
+        let g = Point::generator();
+        let p = g.scalar_mul(&[0xDEADBEEF, 0, 0, 0]);
+        let k = Scalar::new([0x0123456789ABCDEF, 0xFEDCBA9876543210, 0xDEADBEEFCAFEBABE, 0x7FFFFFFFFFFFFFFF]);
+        for point in [g, p] {
+            for scalar in [k, Scalar::from(1), -Scalar::from(1)] {
+                assert_eq!(point.scalar_mul_blinded(&scalar, &mut rng), point.scalar_mul(&scalar.value));
+            }
+        }
+        assert!(g.scalar_mul_blinded(&Scalar::from(0), &mut rng).is_infinity());
+        assert!(Point::infinity().scalar_mul_blinded(&k, &mut rng).is_infinity());
+
+        // Two calls agree on the point but not on its coordinates
+        let first = g.scalar_mul_blinded_jacobian(&k, &mut rng);
+        let second = g.scalar_mul_blinded_jacobian(&k, &mut rng);
+        assert_eq!(first.to_affine(), second.to_affine());
+        assert_ne!(first.x, second.x);
+        assert_ne!(first.y, second.y);
+        assert_ne!(first.z, second.z);
+    }
+
+    #[test]
+    fn test_scalar_mul_variants_agree() {
+        let mut rng = test_rng(0xA54F_F53A);
+
+        // Affine double-and-add, sharing no code with the methods under test
+        let reference = |point: &Point, k: &Scalar| {
+            let mut result = Point::infinity();
+            for i in (0..256).rev() {
+                result = result.double();
+                if k.bit(i) == 1 {
+                    result = result.add(point);
+                }
+            }
+            result
+        };
+
+        let g = Point::generator();
+        let points = [g, g.scalar_mul_vartime(&Scalar::from(0xDEADBEEF)), Point::infinity()];
+        let mut scalars = vec![
+            Scalar::from(0),
+            Scalar::from(1),
+            Scalar::from(2),
+            -Scalar::from(1),
+            -Scalar::from(2),
+            Scalar::new([0, 0, 0, 1 << 63]),
+            Scalar::new([0x0123456789ABCDEF, 0xFEDCBA9876543210, 0xDEADBEEFCAFEBABE, 0x7FFFFFFFFFFFFFFF]),
+        ];
+        for _ in 0..4 {
+            scalars.push(Scalar::new([rng.next_u64(), rng.next_u64(), rng.next_u64(), rng.next_u64()]));
+        }
+
+        for point in &points {
+            for k in &scalars {
+                let expected = reference(point, k);
+                let results = [
+                    ("scalar_mul", point.scalar_mul(&k.value)),
+                    ("scalar_mul_vartime", point.scalar_mul_vartime(k)),
+                    ("scalar_mul_blinded", point.scalar_mul_blinded(k, &mut rng)),
+                    ("JacobianPoint::scalar_mul", JacobianPoint::from_affine(point).scalar_mul(&k.value).to_affine()),
+                    ("multi_scalar_mul_vartime", multi_scalar_mul_vartime(&[(*k, *point)])),
+                    ("mul_add_vartime", point.mul_add_vartime(k, &g, &Scalar::from(0))),
+                    ("Point * Scalar", *point * *k),
+                ];
+                for (name, result) in results {
+                    assert_eq!(result, expected, "{} of {:?} by {:?}", name, point, k);
+                }
+            }
+        }
+
+        // And the generator-only paths
+        for k in &scalars {
+            let expected = reference(&g, k);
+            assert_eq!(Point::mul_generator(k), expected, "mul_generator({:?})", k);
+            assert_eq!(Point::mul_generator_vartime(k), expected, "mul_generator_vartime({:?})", k);
+            #[cfg(feature = "std")]
+            assert_eq!(Point::mul_generator_comb(k), expected, "mul_generator_comb({:?})", k);
+        }
+    }
+
+    #[test]
+    fn test_normalize_all_matches_to_affine() {
+        let g = JacobianPoint::from_affine(&Point::generator());
+        let mut points = vec![g, JacobianPoint::infinity()];
+        for _ in 0..6 {
+            let last = *points.last().unwrap();
+            points.push(last.double().add(&g));
+        }
+        points.push(JacobianPoint::infinity());
+        let expected: Vec<Point> = points.iter().map(|p| p.to_affine()).collect();
+
+        JacobianPoint::normalize_all(&mut points);
+        for (point, affine) in points.iter().zip(&expected) {
+            assert_eq!(point.to_affine(), *affine);
+            if affine.is_infinity() {
+                assert!(point.z.is_zero());
+            } else {
+                assert_eq!(point.z, FieldElement::from(1));
+                assert_eq!((Some(point.x), Some(point.y)), (affine.x, affine.y));
+            }
+        }
+
+        JacobianPoint::normalize_all(&mut []);
+    }
+
+    #[test]
+    fn test_jacobian_chain_matches_affine() {
+        let g = Point::generator();
+        let q = g.scalar_mul(&[0xDEADBEEF, 0, 0, 0]);
+        let k = [0x0123456789ABCDEF, 0xFEDCBA9876543210, 0xDEADBEEFCAFEBABE, 0x7FFFFFFFFFFFFFFF];
+        let minus_one = (-Scalar::from(1)).value;
+
+        // ((2·(G + Q) + G)·k + Q) - Q + infinity, step by step in each form
+        let affine = {
+            let a = g.add(&q).double().add(&g);
+            let b = a.scalar_mul(&k).add(&q);
+            let c = b.add(&q.scalar_mul(&minus_one)).add(&Point::infinity());
+            [a, b, c]
+        };
+        let (jg, jq) = (JacobianPoint::from_affine(&g), JacobianPoint::from_affine(&q));
+        let mut jacobian = {
+            let a = jg.add(&jq).double().add(&jg);
+            let b = a.scalar_mul(&k).add(&jq);
+            let c = b.add(&jq.scalar_mul(&minus_one)).add(&JacobianPoint::infinity());
+            [a, b, c]
+        };
+        for (point, expected) in jacobian.iter().zip(&affine) {
+            assert_eq!(point.to_affine(), *expected);
+        }
+        JacobianPoint::normalize_all(&mut jacobian);
+        for (point, expected) in jacobian.iter().zip(&affine) {
+            assert_eq!((Some(point.x), Some(point.y)), (expected.x, expected.y));
+        }
+
+        // P + (-P) and 2·infinity stay at infinity
+        let negated = jq.scalar_mul(&minus_one);
+        assert!(jq.add(&negated).to_affine().is_infinity());
+        assert!(JacobianPoint::infinity().double().to_affine().is_infinity());
+    }
+
+    #[test]
+    fn test_scalar_reduction() {
+        assert!(Scalar::new(CURVE_ORDER).is_zero());
+        let mut n_plus_five = CURVE_ORDER;
+        n_plus_five[0] += 5;
+        assert_eq!(Scalar::new(n_plus_five), Scalar::new([5, 0, 0, 0]));
+    }
+
+    #[test]
+    fn test_scalar_arithmetic() {
+        let a = Scalar::new([0xDEADBEEF, 0x12345678, 0, 0x8000000000000000]);
+        let one = Scalar::new([1, 0, 0, 0]);
+        assert_eq!(a * a.inv(), one);
+        assert!((a + (-a)).is_zero());
+        assert_eq!(Scalar::from_bytes_reduced(&a.to_bytes()), a);
+    }
+
+    #[test]
+    fn test_scalar_bit_length_and_bits() {
+        let mut n_minus_one = CURVE_ORDER;
+        n_minus_one[0] -= 1;
+        let n_minus_one = Scalar::new(n_minus_one);
+        assert_eq!(Scalar::from(0).bit_length(), 0);
+        assert_eq!(Scalar::from(1).bit_length(), 1);
+        assert_eq!(n_minus_one.bit_length(), 256);
+        assert_eq!(Scalar::from(u64::MAX).bit_length(), 64);
+        assert_eq!(Scalar::new([0, 1, 0, 0]).bit_length(), 65);
+        let mid = Scalar::new([0xDEADBEEF, 0, 0x0000_1000_0000_0000, 0]);
+        assert_eq!(mid.bit_length(), 128 + 45);
+
+        // Bits agree with the big-endian encoding
+        let k = Scalar::new([0x0123456789ABCDEF, 0xFEDCBA9876543210, 0xDEADBEEFCAFEBABE, 0x7FFFFFFFFFFFFFFF]);
+        let bytes = k.to_bytes();
+        for i in 0..256 {
+            assert_eq!(k.bit(i), (bytes[31 - i / 8] >> (i % 8)) & 1, "bit {}", i);
+        }
+        assert_eq!(k.bit(255), 0);
+        assert_eq!(k.bit(254), 1);
+        assert_eq!(k.bit(256), 0);
+        assert_eq!(n_minus_one.bit(usize::MAX), 0);
+    }
+
+    #[test]
+    fn test_scalar_order_matches_bytes() {
+        let k = Scalar::new([0x0123456789ABCDEF, 0xFEDCBA9876543210, 0xDEADBEEFCAFEBABE, 0x7FFFFFFFFFFFFFFF]);
+        let mut scalars = vec![
+            -Scalar::from(1),
+            Scalar::from(0),
+            k,
+            Scalar::from(u64::MAX),
+            Scalar::new([0, 1, 0, 0]),
+            Scalar::from(1),
+            k,
+            Scalar::new([1, 0, 0, 1]),
+            Scalar::new([0, 0, 0, 1]),
+            Scalar::from(0),
+            // Reduced on construction: n + 5 sorts as 5
+            Scalar::new([CURVE_ORDER[0] + 5, CURVE_ORDER[1], CURVE_ORDER[2], CURVE_ORDER[3]]),
+        ];
+        let mut encodings: Vec<[u8; 32]> = scalars.iter().map(|s| s.to_bytes()).collect();
+        scalars.sort();
+        encodings.sort();
+        assert_eq!(scalars.iter().map(|s| s.to_bytes()).collect::<Vec<_>>(), encodings);
+
+        assert_eq!(k.cmp(&k), core::cmp::Ordering::Equal);
+        assert!(Scalar::from(5) < Scalar::new([0, 0, 0, 1]));
+        assert!(-Scalar::from(1) > k);
+    }
+
+    #[test]
+    fn test_signed_scalars_and_subtraction() {
+        let mut n_minus_one = CURVE_ORDER;
+        n_minus_one[0] -= 1;
+        let n_minus_one = Scalar::new(n_minus_one);
+        let zero = Scalar::from(0);
+        let one = Scalar::from(1);
+
+        assert_eq!(zero - one, n_minus_one);
+        assert_eq!(Scalar::from_i64(-1), n_minus_one);
+        assert_eq!(Scalar::from_i64(0), zero);
+        assert_eq!(Scalar::from_i64(42), Scalar::from(42));
+        assert_eq!(Scalar::from_i64(-42), -Scalar::from(42));
+        assert_eq!(Scalar::from_i64(i64::MIN), -Scalar::from(1u64 << 63));
+        assert_eq!(Scalar::from_i64(i64::MAX) + Scalar::from_i64(i64::MIN), n_minus_one);
+
+        let a = Scalar::new([0xDEADBEEF, 0x12345678, 0, 0x8000000000000000]);
+        let b = Scalar::new([0x0123456789ABCDEF, 0xFEDCBA9876543210, 0xDEADBEEFCAFEBABE, 0xFFFFFFFFFFFFFFFF]);
+        for (x, y) in [(a, b), (b, a), (zero, a), (a, zero), (a, a), (n_minus_one, one), (one, n_minus_one)] {
+            assert_eq!(x - y, x + (-y));
+            assert_eq!((x - y) + y, x);
+        }
+
+        // Sums that wrap past n still associate
+        for (x, y, z) in [(a, b, n_minus_one), (n_minus_one, n_minus_one, n_minus_one), (b, b, a)] {
+            assert_eq!((x + y) + z, x + (y + z));
+        }
+    }
+
+    #[test]
+    fn test_scalar_parsing_enforces_order() {
+        let n_minus_one = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140";
+        let n = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+        assert_eq!(Scalar::from_hex(n_minus_one), Ok(-Scalar::from(1)));
+        assert_eq!(Scalar::from_hex(n), Err(Error::ScalarOutOfRange));
+        assert_eq!(Scalar::from_bytes(&[0xFF; 32]), Err(Error::ScalarOutOfRange));
+        // Above n but below p: a valid field element, not a valid scalar
+        let between = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364142";
+        assert_eq!(Scalar::from_hex(between), Err(Error::ScalarOutOfRange));
+        assert!(FieldElement::from_hex(between).is_ok());
+
+        let k = Scalar::new([0x0123456789ABCDEF, 0xFEDCBA9876543210, 0xDEADBEEFCAFEBABE, 0x7FFFFFFFFFFFFFFF]);
+        assert_eq!(Scalar::from_bytes(&k.to_bytes()), Ok(k));
+        assert_eq!(Scalar::from_hex(&hex::encode(&k.to_bytes())), Ok(k));
+        assert_eq!(Scalar::from_hex(&hex::encode(&k.to_bytes()).to_uppercase()), Ok(k));
+
+        for bad in ["", "00", &n_minus_one[1..], "zz", &format!("{}00", n_minus_one)] {
+            assert_eq!(Scalar::from_hex(bad), Err(Error::InvalidEncoding), "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn test_scalar_from_bytes_wide() {
+        // (input, input mod n), reference values from Python integers.
+        // Floor((2^512 - 1) / n)·n is the largest multiple of n that fits.
+        let vectors = [
+            (
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff6298e32a7e39643a19680a1ba432f8398beb693df183078776930deb98282ebf",
+                "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+            ),
+            (
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff6298e32a7e39643a19680a1ba432f8398beb693df183078776930deb98282ec0",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+            ),
+            (
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff6298e32a7e39643a19680a1ba432f8398beb693df183078776930deb98282ec1",
+                "0000000000000000000000000000000000000000000000000000000000000001",
+            ),
+            (
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                "9d671cd581c69bc5e697f5e45bcd07c6741496c20e7cf878896cf21467d7d13f",
+            ),
+            (
+                "0000000000000000000000000000000000000000000000000000000000000000fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+            ),
+            (
+                "00000000000000000000000000000000000000000000000000000000000000010000000000000000000000000000000000000000000000000000000000000000",
+                "000000000000000000000000000000014551231950b75fc4402da1732fc9bebf",
+            ),
+            // SHA-512("abc")
+            (
+                "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f",
+                "f9726d8a91c103c2e7b921ea5462e94cac0a3de5849a441281b991d1fa2ffc3d",
+            ),
+        ];
+        for (input, expected) in vectors {
+            let bytes: [u8; 64] = hex::decode(input).unwrap().try_into().unwrap();
+            let reduced = Scalar::from_bytes_wide(&bytes);
+            assert_eq!(reduced.to_bytes(), hex::decode32(expected), "{}", input);
+        }
+
+        // With a zero high half this is just from_bytes_reduced
+        let mut low_only = [0u8; 64];
+        low_only[32..].copy_from_slice(&[0xFF; 32]);
+        assert_eq!(Scalar::from_bytes_wide(&low_only), Scalar::from_bytes_reduced(&[0xFF; 32]));
+    }
+
+    #[test]
+    fn test_field_element_from_hex() {
+        let x = FieldElement::from_hex("79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798");
+        assert_eq!(x, Ok(FieldElement::new(GENERATOR_X)));
+        let p = "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f";
+        assert_eq!(FieldElement::from_hex(p), Err(Error::InvalidEncoding));
+    }
+
+    #[test]
+    fn test_fallible_constructors() {
+        assert_eq!(FieldElement::from_bytes(&limbs_to_bytes(&GENERATOR_X)), Ok(Point::generator().x.unwrap()));
+        assert_eq!(FieldElement::from_bytes(&limbs_to_bytes(&FIELD_MODULUS)), Err(Error::InvalidEncoding));
+        assert_eq!(FieldElement::from_bytes(&[0xFF; 32]), Err(Error::InvalidEncoding));
+
+        assert_eq!(Scalar::new([0, 0, 0, 0]).try_inv(), Err(Error::ZeroScalar));
+        let a = Scalar::new([3, 0, 0, 0]);
+        assert_eq!(a.try_inv(), Ok(a.inv()));
+        assert_eq!(FieldElement::from(0).try_inv(), Err(Error::ZeroScalar));
+        let b = FieldElement::from(3);
+        assert_eq!(b.try_inv(), Ok(b.inv()));
+    }
+
+    #[test]
+    fn test_error_display() {
+        let errors = [
+            Error::ZeroScalar,
+            Error::WeakKey,
+            Error::InvalidNonce,
+            Error::InvalidEncoding,
+            Error::InvalidChecksum,
+            Error::NotOnCurve,
+            Error::ScalarOutOfRange,
+            Error::PointAtInfinity,
+            Error::InvalidSignature,
+        ];
+        let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        for (i, message) in messages.iter().enumerate() {
+            assert!(!message.is_empty());
+            assert!(!messages[..i].contains(message), "duplicate message {:?}", message);
+        }
+
+        // Usable as a boxed standard error
+        let boxed: Box<dyn std::error::Error> = Box::new(Error::NotOnCurve);
+        assert_eq!(boxed.to_string(), "point is not on the curve");
+    }
+
+    #[test]
+    fn test_field_elements_stay_canonical() {
+        let mut rng = test_rng(0x2545_F491);
+        let mut next = move || rng.next_u64();
+
+        // The values just above and below p are the ones a broken reduction
+        // gets wrong, so mix them in with uniformly random ones
+        let mut p_plus_one = FIELD_MODULUS;
+        p_plus_one[0] += 1;
+        let mut p_minus_one = FIELD_MODULUS;
+        p_minus_one[0] -= 1;
+        let mut inputs = vec![FIELD_MODULUS, p_plus_one, p_minus_one, [u64::MAX; 4], [0; 4]];
+        for _ in 0..1000 {
+            inputs.push([next(), next(), next(), next()]);
+            // Top limbs all ones, so the value lands in [p, 2^256) more often
+            inputs.push([next(), u64::MAX, u64::MAX, u64::MAX]);
+        }
+
+        let mut prev = FieldElement::from(1);
+        for value in inputs {
+            let a = FieldElement::new(value);
+            assert!(a.is_valid(), "new({:x?}) = {:x?}", value, a.value);
+            for result in [a + prev, a - prev, a * prev, -a, a * a] {
+                assert!(result.is_valid(), "{:x?} and {:x?} gave {:x?}", a.value, prev.value, result.value);
+            }
+            prev = a;
+        }
+        assert!(FieldElement::new(FIELD_MODULUS).is_zero());
+        assert_eq!(FieldElement::new(p_plus_one), FieldElement::from(1));
+        assert!(!FieldElement { value: FIELD_MODULUS }.is_valid());
+    }
+
+    #[test]
+    fn test_field_element_from() {
+        assert_eq!(FieldElement::from(7u64), FieldElement::new([7, 0, 0, 0]));
+        assert_eq!(FieldElement::from(u64::MAX), FieldElement::new([u64::MAX, 0, 0, 0]));
+        assert_eq!(FieldElement::from(limbs_to_bytes(&GENERATOR_X)), Point::generator().x.unwrap());
+
+        let mut bytes = [0u8; 32];
+        bytes[31] = 7;
+        assert_eq!(FieldElement::from(bytes), FieldElement::from(7));
+    }
+
+    #[test]
+    #[should_panic(expected = "not below p")]
+    fn test_field_element_from_bytes_panics_on_overflow() {
+        let _ = FieldElement::from(limbs_to_bytes(&FIELD_MODULUS));
+    }
+}
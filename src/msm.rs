@@ -0,0 +1,280 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{JacobianPoint, Point, Scalar};
+
+// Above this many terms the bucket method beats interleaved wNAF
+const PIPPENGER_THRESHOLD: usize = 32;
+
+// wNAF window for the interleaved method: digits are odd and below 2^(W-1)
+// in absolute value, so each point needs 2^(W-2) precomputed odd multiples
+const WNAF_WIDTH: u32 = 5;
+
+// Σ k_i·P_i over all pairs, infinity for an empty slice. Up to
+// PIPPENGER_THRESHOLD terms this interleaves width-5 wNAF expansions over
+// one shared chain of doublings; above it, Pippenger's bucket method, whose
+// cost per term shrinks as the number of terms grows. Both are variable
+// time; use them on public data such as verification equations.
+pub fn multi_scalar_mul_vartime(pairs: &[(Scalar, Point)]) -> Point {
+    if pairs.len() > PIPPENGER_THRESHOLD {
+        pippenger(pairs).to_affine()
+    } else {
+        interleaved_wnaf(pairs).to_affine()
+    }
+}
+
+// Width-w non-adjacent form, least significant digit first: k = Σ d_i·2^i
+// with each non-zero d_i odd and |d_i| < 2^(w-1), and any w consecutive
+// digits holding at most one non-zero
+pub(crate) fn wnaf(scalar: &Scalar, width: u32) -> Vec<i32> {
+    // A fifth limb absorbs the carry when a negative digit is taken off
+    let mut k = [
+        scalar.value[0],
+        scalar.value[1],
+        scalar.value[2],
+        scalar.value[3],
+        0,
+    ];
+    let window = 1u64 << width;
+    let mut digits = Vec::with_capacity(257);
+    while k != [0; 5] {
+        let mut digit = 0i32;
+        if k[0] & 1 == 1 {
+            let low = k[0] & (window - 1);
+            if low >= window / 2 {
+                // k - (low - 2^w) = k + (2^w - low)
+                digit = low as i32 - window as i32;
+                add_small(&mut k, window - low);
+            } else {
+                digit = low as i32;
+                k[0] -= low;
+            }
+        }
+        digits.push(digit);
+        shift_right_one(&mut k);
+    }
+    digits
+}
+
+fn add_small(k: &mut [u64; 5], value: u64) {
+    let mut carry = value;
+    for limb in k.iter_mut() {
+        let (sum, overflow) = limb.overflowing_add(carry);
+        *limb = sum;
+        carry = overflow as u64;
+    }
+}
+
+fn shift_right_one(k: &mut [u64; 5]) {
+    for i in 0..5 {
+        let next = if i < 4 { k[i + 1] << 63 } else { 0 };
+        k[i] = (k[i] >> 1) | next;
+    }
+}
+
+// The odd multiples P, 3P, ..., (2^(w-1) - 1)·P for wNAF lookups
+pub(crate) fn odd_multiples(point: &JacobianPoint, width: u32) -> Vec<JacobianPoint> {
+    let count = 1 << (width - 2);
+    let twice = point.double();
+    let mut multiples = Vec::with_capacity(count);
+    multiples.push(*point);
+    for i in 1..count {
+        let next = multiples[i - 1].add(&twice);
+        multiples.push(next);
+    }
+    multiples
+}
+
+// Add digit·P given P's odd multiples; digit is odd, or zero for no-op
+pub(crate) fn add_wnaf_digit(
+    acc: &JacobianPoint,
+    multiples: &[JacobianPoint],
+    digit: i32,
+) -> JacobianPoint {
+    if digit == 0 {
+        return *acc;
+    }
+    let mut term = multiples[(digit.unsigned_abs() / 2) as usize];
+    if digit < 0 {
+        term.y = -term.y;
+    }
+    acc.add(&term)
+}
+
+pub(crate) fn interleaved_wnaf(pairs: &[(Scalar, Point)]) -> JacobianPoint {
+    let terms: Vec<(Vec<i32>, Vec<JacobianPoint>)> = pairs
+        .iter()
+        .filter(|(k, p)| !k.is_zero() && !p.is_infinity())
+        .map(|(k, p)| {
+            (
+                wnaf(k, WNAF_WIDTH),
+                odd_multiples(&JacobianPoint::from_affine(p), WNAF_WIDTH),
+            )
+        })
+        .collect();
+    let length = terms
+        .iter()
+        .map(|(digits, _)| digits.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut acc = JacobianPoint::infinity();
+    for i in (0..length).rev() {
+        acc = acc.double();
+        for (digits, multiples) in &terms {
+            if let Some(&digit) = digits.get(i) {
+                acc = add_wnaf_digit(&acc, multiples, digit);
+            }
+        }
+    }
+    acc
+}
+
+// Bucket method: cut every scalar into c-bit windows. For each window, from
+// the top, throw each point into the bucket numbered by its digit, then
+// Σ j·bucket_j falls out of a running sum over the buckets in two additions
+// per bucket. Each window costs about n + 2^(c+1) additions in place of
+// n scalar multiplication steps.
+fn pippenger(pairs: &[(Scalar, Point)]) -> JacobianPoint {
+    if pairs.is_empty() {
+        return JacobianPoint::infinity();
+    }
+    let c = pairs.len().ilog2().clamp(2, 16) as usize;
+    let points: Vec<JacobianPoint> = pairs
+        .iter()
+        .map(|(_, p)| JacobianPoint::from_affine(p))
+        .collect();
+    let digit = |k: &Scalar, window: usize| -> usize {
+        (0..c)
+            .map(|bit| (k.bit(window * c + bit) as usize) << bit)
+            .sum()
+    };
+
+    let mut acc = JacobianPoint::infinity();
+    for window in (0..256usize.div_ceil(c)).rev() {
+        for _ in 0..c {
+            acc = acc.double();
+        }
+        let mut buckets = vec![JacobianPoint::infinity(); (1 << c) - 1];
+        for ((k, _), point) in pairs.iter().zip(&points) {
+            let d = digit(k, window);
+            if d != 0 {
+                buckets[d - 1] = buckets[d - 1].add(point);
+            }
+        }
+        // running = Σ_{i >= j} bucket_i, summed over j, is Σ j·bucket_j
+        let mut running = JacobianPoint::infinity();
+        let mut window_sum = JacobianPoint::infinity();
+        for bucket in buckets.iter().rev() {
+            running = running.add(bucket);
+            window_sum = window_sum.add(&running);
+        }
+        acc = acc.add(&window_sum);
+    }
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_vectors::test_rng;
+    use rand_chacha::rand_core::RngCore;
+    use rand_chacha::ChaCha8Rng;
+
+    fn random_scalar(rng: &mut ChaCha8Rng) -> Scalar {
+        let mut bytes = [0u8; 32];
+        rng.fill_bytes(&mut bytes);
+        Scalar::from_bytes_reduced(&bytes)
+    }
+
+    fn random_pairs(rng: &mut ChaCha8Rng, count: usize) -> Vec<(Scalar, Point)> {
+        (0..count)
+            .map(|_| {
+                let k = random_scalar(rng);
+                let p = Point::mul_generator(&random_scalar(rng));
+                (k, p)
+            })
+            .collect()
+    }
+
+    fn naive(pairs: &[(Scalar, Point)]) -> Point {
+        pairs.iter().fold(Point::infinity(), |acc, (k, p)| {
+            acc.add(&p.scalar_mul(&k.value))
+        })
+    }
+
+    #[test]
+    fn test_wnaf_recoding() {
+        let mut rng = test_rng(0x1F83_D9AB);
+        let mut scalars = vec![
+            Scalar::from(0),
+            Scalar::from(1),
+            Scalar::from(31),
+            -Scalar::from(1),
+        ];
+        scalars.extend((0..20).map(|_| random_scalar(&mut rng)));
+        for k in scalars {
+            let digits = wnaf(&k, WNAF_WIDTH);
+            assert!(digits.len() <= 257);
+            let mut value = Scalar::from(0);
+            for (i, &d) in digits.iter().enumerate().rev() {
+                value = value + value + Scalar::from_i64(d as i64);
+                if d != 0 {
+                    assert!(d % 2 != 0 && d.abs() < 16, "digit {} at {}", d, i);
+                    assert!(digits[i + 1..].iter().take(4).all(|&next| next == 0));
+                }
+            }
+            assert_eq!(value, k);
+        }
+    }
+
+    #[test]
+    fn test_multi_scalar_mul_matches_naive_sum() {
+        let mut rng = test_rng(0x5BE0_CD19);
+        for count in [1, 2, 10, 100] {
+            let pairs = random_pairs(&mut rng, count);
+            let expected = naive(&pairs);
+            assert_eq!(multi_scalar_mul_vartime(&pairs), expected, "{} terms", count);
+            assert_eq!(
+                interleaved_wnaf(&pairs).to_affine(),
+                expected,
+                "{} terms",
+                count
+            );
+            assert_eq!(pippenger(&pairs).to_affine(), expected, "{} terms", count);
+        }
+    }
+
+    #[test]
+    fn test_multi_scalar_mul_edge_cases() {
+        assert!(multi_scalar_mul_vartime(&[]).is_infinity());
+        assert!(pippenger(&[]).to_affine().is_infinity());
+
+        // Duplicate points, cancelling terms, zero scalars and infinity
+        let g = Point::generator();
+        let k = Scalar::new([
+            0x0123456789ABCDEF,
+            0xFEDCBA9876543210,
+            0xDEADBEEFCAFEBABE,
+            0x7FFFFFFFFFFFFFFF,
+        ]);
+        let mut pairs = vec![
+            (k, g),
+            (k, g),
+            (Scalar::from(3), g),
+            (Scalar::from(0), g),
+            (k, Point::infinity()),
+        ];
+        let expected = g.scalar_mul(&(k + k + Scalar::from(3)).value);
+        assert_eq!(multi_scalar_mul_vartime(&pairs), expected);
+        assert_eq!(pippenger(&pairs).to_affine(), expected);
+
+        pairs.push((-(k + k + Scalar::from(3)), g));
+        assert!(multi_scalar_mul_vartime(&pairs).is_infinity());
+        assert!(pippenger(&pairs).to_affine().is_infinity());
+
+        // Forty copies of one point go through the bucket method
+        let many = vec![(Scalar::from(5), g); 40];
+        assert_eq!(multi_scalar_mul_vartime(&many), g.scalar_mul(&[200, 0, 0, 0]));
+    }
+}
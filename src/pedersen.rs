@@ -0,0 +1,75 @@
+use crate::{FieldElement, Point, Scalar};
+
+// Second generator for Pedersen commitments:
+// hash_to_curve(b"H", b"SECP256K1-PEDERSEN-V01_XMD:SHA-256_SSWU_RO_").
+// Being a hash output, nobody knows its discrete log relative to G, which
+// is what makes a commitment binding.
+pub const PEDERSEN_H: Point = Point {
+    x: Some(FieldElement {
+        value: [
+            0x5678238384BE781D,
+            0x1A8D4CA979B245DA,
+            0x072EA687CB7D47CD,
+            0x86A2C420EA836824,
+        ],
+    }),
+    y: Some(FieldElement {
+        value: [
+            0x513C6426E6AFD2BC,
+            0x2B6B819AEA7E20CD,
+            0x5C598E8483D50ED3,
+            0x0525D59751D042B8,
+        ],
+    }),
+};
+
+// Pedersen commitment value·G + blinding·H. With blinding uniformly random
+// the commitment hides value, and commitments add: C(v1, b1) + C(v2, b2) =
+// C(v1 + v2, b1 + b2). h is normally PEDERSEN_H.
+pub fn pedersen_commit(value: &Scalar, blinding: &Scalar, h: &Point) -> Point {
+    Point::mul_generator(value).add(&(*h * *blinding))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generator_h() {
+        #[cfg(feature = "hashes")]
+        assert_eq!(crate::hash_to_curve(b"H", b"SECP256K1-PEDERSEN-V01_XMD:SHA-256_SSWU_RO_"), PEDERSEN_H);
+        assert!(PEDERSEN_H.is_on_curve());
+        assert_ne!(PEDERSEN_H, Point::generator());
+    }
+
+    #[test]
+    fn test_commitments_are_homomorphic() {
+        let v1 = Scalar::from(25_000);
+        let v2 = Scalar::from(17_500);
+        let b1 = Scalar::new([
+            0x0123456789ABCDEF,
+            0xFEDCBA9876543210,
+            0xDEADBEEFCAFEBABE,
+            0x7FFFFFFFFFFFFFFF,
+        ]);
+        let b2 = -Scalar::from(3);
+
+        let c1 = pedersen_commit(&v1, &b1, &PEDERSEN_H);
+        let c2 = pedersen_commit(&v2, &b2, &PEDERSEN_H);
+        assert_eq!(
+            c1 + c2,
+            pedersen_commit(&(v1 + v2), &(b1 + b2), &PEDERSEN_H)
+        );
+
+        // A commitment and its negated-value counterpart cancel the value
+        let c3 = pedersen_commit(&-v1, &b2, &PEDERSEN_H);
+        assert_eq!(c1 + c3, PEDERSEN_H * (b1 + b2));
+
+        // Zero blinding leaves a bare value·G; different blindings hide it
+        assert_eq!(
+            pedersen_commit(&v1, &Scalar::from(0), &PEDERSEN_H),
+            Point::generator() * v1
+        );
+        assert_ne!(c1, pedersen_commit(&v1, &b2, &PEDERSEN_H));
+    }
+}
@@ -0,0 +1,136 @@
+use crate::hashes::{hmac_sha256, HmacSha256};
+use crate::{geq_limbs, limbs_from_bytes, Scalar, CURVE_ORDER};
+
+// HMAC_DRBG state from RFC 6979 section 3.2, specialised to SHA-256 and a
+// 256-bit group order (qlen = hlen, so each candidate is a single block)
+struct NonceGenerator {
+    k: [u8; 32],
+    v: [u8; 32],
+    // Whether a candidate has already been produced and rejected
+    started: bool,
+}
+
+impl NonceGenerator {
+    fn new(privkey: &Scalar, msg_hash: &[u8; 32]) -> Self {
+        // int2octets(x) || bits2octets(h1), where bits2octets reduces h1 mod n
+        let x = privkey.to_bytes();
+        let h = Scalar::from_bytes_reduced(msg_hash).to_bytes();
+
+        let mut generator = NonceGenerator {
+            k: [0u8; 32],
+            v: [1u8; 32],
+            started: false,
+        };
+        generator.reseed(0x00, &[&x, &h]);
+        generator.reseed(0x01, &[&x, &h]);
+        generator
+    }
+
+    // K = HMAC_K(V || tag || data), V = HMAC_K(V)
+    fn reseed(&mut self, tag: u8, data: &[&[u8]]) {
+        let mut mac = HmacSha256::new(&self.k);
+        mac.update(&self.v);
+        mac.update(&[tag]);
+        for part in data {
+            mac.update(part);
+        }
+        self.k = mac.finalize();
+        self.v = hmac_sha256(&self.k, &self.v);
+    }
+
+    // Next candidate T; after a rejection the state is first advanced
+    fn next_candidate(&mut self) -> [u8; 32] {
+        if self.started {
+            self.reseed(0x00, &[]);
+        }
+        self.started = true;
+        self.v = hmac_sha256(&self.k, &self.v);
+        self.v
+    }
+}
+
+// K and V determine every later candidate, so they are as sensitive as the
+// nonce itself
+#[cfg(feature = "zeroize")]
+impl Drop for NonceGenerator {
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+        self.k.zeroize();
+        self.v.zeroize();
+    }
+}
+
+// Deterministic ECDSA nonce from RFC 6979 using HMAC-SHA256. Candidates equal
+// to zero or >= n are rejected and the generator is iterated.
+pub fn rfc6979_nonce(privkey: &Scalar, msg_hash: &[u8; 32]) -> Scalar {
+    let mut generator = NonceGenerator::new(privkey, msg_hash);
+    loop {
+        let candidate = limbs_from_bytes(&generator.next_candidate());
+        if candidate != [0u64; 4] && !geq_limbs(&candidate, &CURVE_ORDER) {
+            return Scalar::new(candidate);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    fn scalar(s: &str) -> Scalar {
+        Scalar::from_bytes_reduced(&hex::decode32(s))
+    }
+
+    #[test]
+    fn test_rfc6979_vectors() {
+        // Widely used secp256k1/SHA-256 vectors (RFC 6979 itself only covers
+        // the NIST curves); the message hash is sha256 of the quoted string
+        let vectors = [
+            (
+                // "Satoshi Nakamoto"
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "a0dc65ffca799873cbea0ac274015b9526505daaaed385155425f7337704883e",
+                "8f8a276c19f4149656b280621e358cce24f5f52542772691ee69063b74f15d15",
+            ),
+            (
+                // "Satoshi Nakamoto"
+                "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364140",
+                "a0dc65ffca799873cbea0ac274015b9526505daaaed385155425f7337704883e",
+                "33a19b60e25fb6f4435af53a3d42d493644827367e6453928554f43e49aa6f90",
+            ),
+            (
+                // "All those moments will be lost in time, like tears in rain. Time to die..."
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "7d1833f54854ac51659521afcd0ec6dca2ce2351429614bfa28a756b1b3c637f",
+                "38aa22d72376b4dbc472e06c3ba403ee0a394da63fc58d88686c611aba98d6b3",
+            ),
+            (
+                // "Alan Turing"
+                "f8b8af8ce3c7cca5e300d33939540c10d45ce001b8f252bfbc57ba0342904181",
+                "4ba38d48a60f1b29e9eb726eaff08b2e83d8d81e031666fee50e85900d7dc1ef",
+                "525a82b70e67874398067543fd84c83d30c175fdc45fdeee082fe13b1d7cfdf1",
+            ),
+        ];
+
+        for (privkey, msg_hash, expected) in vectors {
+            let nonce = rfc6979_nonce(&scalar(privkey), &hex::decode32(msg_hash));
+            assert_eq!(nonce, scalar(expected));
+        }
+    }
+
+    #[test]
+    fn test_generator_iterates_after_rejection() {
+        // The candidate following a rejected one, per step 3.2.h.3
+        let privkey = scalar("0000000000000000000000000000000000000000000000000000000000000001");
+        let msg_hash = hex::decode32("a0dc65ffca799873cbea0ac274015b9526505daaaed385155425f7337704883e");
+        let mut generator = NonceGenerator::new(&privkey, &msg_hash);
+        assert_eq!(
+            generator.next_candidate(),
+            hex::decode32("8f8a276c19f4149656b280621e358cce24f5f52542772691ee69063b74f15d15")
+        );
+        assert_eq!(
+            generator.next_candidate(),
+            hex::decode32("f15fb763a6bcbbacbde0a6a9ae2a02482bd92f3e75a50b357bd551ddd771045e")
+        );
+    }
+}
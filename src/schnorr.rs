@@ -0,0 +1,237 @@
+use crate::hashes::{tagged_hash, tagged_hasher};
+use crate::{geq_limbs, limbs_from_bytes, Error, FieldElement, Point, PrivateKey, Scalar, XOnlyPublicKey, CURVE_ORDER};
+
+// Tagged hash of the concatenation of several parts
+fn tagged_hash_parts(tag: &str, parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = tagged_hasher(tag);
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize()
+}
+
+// x coordinate and y parity of a point known not to be infinity
+fn x_and_odd(point: &Point) -> ([u8; 32], bool) {
+    let x = point.x.expect("point at infinity");
+    let y = point.y.expect("point at infinity");
+    (x.to_bytes(), y.is_odd())
+}
+
+// BIP340 Schnorr signature (R.x, s) over a 32-byte message. The public key is
+// the x coordinate of d·G and the signer uses whichever of d and n - d gives
+// it an even y. aux_rand should be fresh randomness; all zeros is still
+// safe, with nonces then depending only on the key and message.
+pub fn schnorr_sign(privkey: &PrivateKey, msg: &[u8; 32], aux_rand: &[u8; 32]) -> Result<[u8; 64], Error> {
+    let (pubkey_x, odd) = x_and_odd(privkey.public_key().as_point());
+    let mut d = *privkey.as_scalar();
+    d.conditional_negate(odd as u8);
+
+    // Mask the key with the hashed auxiliary randomness before deriving the
+    // nonce, so that a biased aux_rand cannot leak it
+    let aux_hash = tagged_hash("BIP0340/aux", aux_rand);
+    let mut t = d.to_bytes();
+    for (byte, mask) in t.iter_mut().zip(aux_hash) {
+        *byte ^= mask;
+    }
+    let rand = tagged_hash_parts("BIP0340/nonce", &[&t, &pubkey_x, msg]);
+    let k = Scalar::from_bytes_reduced(&rand);
+    if k.is_zero() {
+        return Err(Error::InvalidNonce);
+    }
+
+    let (r, odd) = x_and_odd(&Point::mul_generator(&k));
+    let mut k = k;
+    k.conditional_negate(odd as u8);
+
+    let e = Scalar::from_bytes_reduced(&tagged_hash_parts("BIP0340/challenge", &[&r, &pubkey_x, msg]));
+    let s = k + e * d;
+
+    let mut sig = [0u8; 64];
+    sig[..32].copy_from_slice(&r);
+    sig[32..].copy_from_slice(&s.to_bytes());
+    Ok(sig)
+}
+
+// Verify a BIP340 signature against an x-only public key. The key is lifted
+// to the point with that x and an even y, then R = s·G - e·P must be a real
+// point with even y whose x equals r.
+pub fn schnorr_verify(pubkey_x: &[u8; 32], msg: &[u8; 32], sig: &[u8; 64]) -> bool {
+    let pubkey = match XOnlyPublicKey::from_bytes(pubkey_x).lift_x() {
+        Some(point) => point,
+        None => return false,
+    };
+
+    // r must be a field element and s a scalar; neither is reduced
+    let (r, s) = sig.split_at(32);
+    let r = match FieldElement::from_bytes(r.try_into().unwrap()) {
+        Ok(r) => r,
+        Err(_) => return false,
+    };
+    let s = limbs_from_bytes(s.try_into().unwrap());
+    if geq_limbs(&s, &CURVE_ORDER) {
+        return false;
+    }
+
+    let e = Scalar::from_bytes_reduced(&tagged_hash_parts("BIP0340/challenge", &[&sig[..32], pubkey_x, msg]));
+    let point = Point::generator().mul_add_vartime(&Scalar::new(s), &pubkey, &-e);
+    if point.is_infinity() {
+        return false;
+    }
+    let (x, odd) = x_and_odd(&point);
+    !odd && x == r.to_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hex;
+
+    #[test]
+    fn test_bip340_sign_vectors() {
+        // Vectors 0-3 from bip-0340/test-vectors.csv:
+        // (secret key, public key, aux_rand, message, signature)
+        let vectors = [
+            (
+                "0000000000000000000000000000000000000000000000000000000000000003",
+                "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "e907831f80848d1069a5371b402410364bdf1c5f8307b0084c55f1ce2dca8215\
+                 25f66a4a85ea8b71e482a74f382d2ce5ebeee8fdb2172f477df4900d310536c0",
+            ),
+            (
+                "b7e151628aed2a6abf7158809cf4f3c762e7160f38b4da56a784d9045190cfef",
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "6896bd60eeae296db48a229ff71dfe071bde413e6d43f917dc8dcf8c78de3341\
+                 8906d11ac976abccb20b091292bff4ea897efcb639ea871cfa95f6de339e4b0a",
+            ),
+            (
+                "c90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74020bbea63b14e5c9",
+                "dd308afec5777e13121fa72b9cc1b7cc0139715309b086c960e18fd969774eb8",
+                "c87aa53824b4d7ae2eb035a2b5bbbccc080e76cdc6d1692c4b0b62d798e6d906",
+                "7e2d58d8b3bcdf1abadec7829054f90dda9805aab56c77333024b9d0a508b75c",
+                "5831aaeed7b44bb74e5eab94ba9d4294c49bcf2a60728d8b4c200f50dd313c1b\
+                 ab745879a5ad954a72c45a91c3a51d3c7adea98d82f8481e0e1e03674a6f3fb7",
+            ),
+            (
+                // Fails if the message is reduced mod p or n
+                "0b432b2677937381aef05bb02a66ecd012773062cf3fa2549e44f58ed2401710",
+                "25d1dff95105f5253c4022f628a996ad3a0d95fbf21d468a1b33f8c160d8f517",
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                "7eb0509757e246f19449885651611cb965ecc1a187dd51b64fda1edc9637d5ec\
+                 97582b9cb13db3933705b32ba982af5af25fd78881ebb32771fc5922efc66ea3",
+            ),
+        ];
+
+        for (secret, pubkey, aux_rand, msg, expected) in vectors {
+            let privkey = PrivateKey::from_bytes(&hex::decode32(secret)).unwrap();
+            assert_eq!(x_and_odd(privkey.public_key().as_point()).0, hex::decode32(pubkey));
+            let sig = schnorr_sign(&privkey, &hex::decode32(msg), &hex::decode32(aux_rand)).unwrap();
+            assert_eq!(sig.to_vec(), hex::decode(expected).unwrap());
+            assert!(schnorr_verify(&hex::decode32(pubkey), &hex::decode32(msg), &sig));
+        }
+    }
+
+    #[test]
+    fn test_bip340_verify_vectors() {
+        // Vectors 4-14 from bip-0340/test-vectors.csv:
+        // (public key, message, signature, expected result)
+        let vectors = [
+            (
+                "d69c3509bb99e412e68b0fe8544e72837dfa30746d8be2aa65975f29d22dc7b9",
+                "4df3c3f68fcc83b27e9d42c90431a72499f17875c81a599b566c9889b9696703",
+                "00000000000000000000003b78ce563f89a0ed9414f5aa28ad0d96d6795f9c63\
+                 76afb1548af603b3eb45c9f8207dee1060cb71c04e80f593060b07d28308d7f4",
+                true,
+            ),
+            (
+                // Public key not on the curve
+                "eefdea4cdb677750a420fee807eacf21eb9898ae79b9768766e4faa04a2d4a34",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "6cff5c3ba86c69ea4b7376f31a9bcb4f74c1976089b2d9963da2e5543e177769\
+                 69e89b4c5564d00349106b8497785dd7d1d713a8ae82b32fa79d5f7fc407d39b",
+                false,
+            ),
+            (
+                // R has odd y
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "fff97bd5755eeea420453a14355235d382f6472f8568a18b2f057a1460297556\
+                 3cc27944640ac607cd107ae10923d9ef7a73c643e166be5ebeafa34b1ac553e2",
+                false,
+            ),
+            (
+                // Negated message
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "1fa62e331edbc21c394792d2ab1100a7b432b013df3f6ff4f99fcb33e0e1515f\
+                 28890b3edb6e7189b630448b515ce4f8622a954cfe545735aaea5134fccdb2bd",
+                false,
+            ),
+            (
+                // Negated s
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "6cff5c3ba86c69ea4b7376f31a9bcb4f74c1976089b2d9963da2e5543e177769\
+                 961764b3aa9b2ffcb6ef947b6887a226e8d7c93e00c5ed0c1834ff0d0c2e6da6",
+                false,
+            ),
+            (
+                // s·G - e·P is infinity, with r = 0
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "0000000000000000000000000000000000000000000000000000000000000000\
+                 123dda8328af9c23a94c1feecfd123ba4fb73476f0d594dcb65c6425bd186051",
+                false,
+            ),
+            (
+                // s·G - e·P is infinity, with r = 1
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "0000000000000000000000000000000000000000000000000000000000000001\
+                 7615fbaf5ae28864013c099742deadb4dba87f11ac6754f93780d5a1837cf197",
+                false,
+            ),
+            (
+                // r is not the x coordinate of any curve point
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "4a298dacae57395a15d0795ddbfd1dcb564da82b0f269bc70a74f8220429ba1d\
+                 69e89b4c5564d00349106b8497785dd7d1d713a8ae82b32fa79d5f7fc407d39b",
+                false,
+            ),
+            (
+                // r = p
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f\
+                 69e89b4c5564d00349106b8497785dd7d1d713a8ae82b32fa79d5f7fc407d39b",
+                false,
+            ),
+            (
+                // s = n
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "6cff5c3ba86c69ea4b7376f31a9bcb4f74c1976089b2d9963da2e5543e177769\
+                 fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+                false,
+            ),
+            (
+                // Public key x exceeds the field size
+                "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc30",
+                "243f6a8885a308d313198a2e03707344a4093822299f31d0082efa98ec4e6c89",
+                "6cff5c3ba86c69ea4b7376f31a9bcb4f74c1976089b2d9963da2e5543e177769\
+                 69e89b4c5564d00349106b8497785dd7d1d713a8ae82b32fa79d5f7fc407d39b",
+                false,
+            ),
+        ];
+
+        for (pubkey, msg, sig, valid) in vectors {
+            let sig: [u8; 64] = hex::decode(sig).unwrap().try_into().unwrap();
+            assert_eq!(schnorr_verify(&hex::decode32(pubkey), &hex::decode32(msg), &sig), valid);
+        }
+    }
+}
@@ -0,0 +1,124 @@
+// serde support: hex strings for human-readable formats, raw bytes otherwise.
+// Public keys use compressed SEC1 and signatures use DER; both are parsed
+// back with the same validation as from_slice and from_der.
+
+use core::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use crate::{hex, PublicKey, Signature};
+
+fn serialize_bytes<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&hex::encode(bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+// Accepts a hex string or raw bytes and hands the bytes to a parser
+struct BytesVisitor<T> {
+    expecting: &'static str,
+    parse: fn(&[u8]) -> Result<T, crate::Error>,
+}
+
+impl<T> Visitor<'_> for BytesVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+        let bytes = hex::decode(value).ok_or_else(|| E::invalid_value(de::Unexpected::Str(value), &self))?;
+        (self.parse)(&bytes).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<T, E> {
+        (self.parse)(value).map_err(E::custom)
+    }
+}
+
+fn deserialize_bytes<'de, D: Deserializer<'de>, T>(
+    deserializer: D,
+    expecting: &'static str,
+    parse: fn(&[u8]) -> Result<T, crate::Error>,
+) -> Result<T, D::Error> {
+    let visitor = BytesVisitor { expecting, parse };
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(visitor)
+    } else {
+        deserializer.deserialize_bytes(visitor)
+    }
+}
+
+impl Serialize for PublicKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&self.serialize(true), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_bytes(deserializer, "a hex or binary SEC1 public key", PublicKey::from_slice)
+    }
+}
+
+impl Serialize for Signature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&self.to_der(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Signature {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_bytes(deserializer, "a hex or binary DER signature", Signature::from_der)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_vectors::test_rng;
+    use crate::{sign_ecdsa, PrivateKey, PublicKey, Signature};
+
+    fn test_key() -> PrivateKey {
+        PrivateKey::from_bytes(&crate::hex::decode32(
+            "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721",
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_public_key_json_round_trip() {
+        let pubkey = test_key().public_key();
+        let json = serde_json::to_string(&pubkey).unwrap();
+        assert_eq!(json, format!("\"{}\"", crate::hex::encode(&pubkey.serialize(true))));
+        assert_eq!(serde_json::from_str::<PublicKey>(&json).unwrap(), pubkey);
+
+        // The uncompressed encoding is accepted on input too
+        let uncompressed = format!("\"{}\"", crate::hex::encode(&pubkey.serialize(false)));
+        assert_eq!(serde_json::from_str::<PublicKey>(&uncompressed).unwrap(), pubkey);
+    }
+
+    #[test]
+    fn test_signature_json_round_trip() {
+        let nonce = PrivateKey::random(&mut test_rng(0xBB67_AE85));
+        let sig = sign_ecdsa(test_key().as_scalar(), &[0x42; 32], nonce.as_scalar()).unwrap();
+        let json = serde_json::to_string(&sig).unwrap();
+        assert_eq!(json, format!("\"{}\"", crate::hex::encode(&sig.to_der())));
+        assert_eq!(serde_json::from_str::<Signature>(&json).unwrap(), sig);
+    }
+
+    #[test]
+    fn test_invalid_input_rejected() {
+        // Not hex, odd length, and hex that is not a valid key or signature
+        assert!(serde_json::from_str::<PublicKey>("\"zz\"").is_err());
+        assert!(serde_json::from_str::<PublicKey>("\"02a\"").is_err());
+        let off_curve = format!("\"02{}\"", "00".repeat(31) + "05");
+        assert!(serde_json::from_str::<PublicKey>(&off_curve).is_err());
+        assert!(serde_json::from_str::<Signature>("\"3006020101020100\"").is_err());
+        assert!(serde_json::from_str::<Signature>("\"300602010102\"").is_err());
+        assert!(serde_json::from_str::<Signature>("7").is_err());
+    }
+}
@@ -0,0 +1,223 @@
+use std::sync::OnceLock;
+
+use crate::comb::{global_comb_table, install_comb_table, CombTable, COMB_BYTES};
+use crate::hashes::sha256;
+use crate::{Error, FieldElement, JacobianPoint, Point, Scalar};
+
+// Fixed-window table for the generator: window i holds j·16^i·G for
+// j = 1..=15, so k·G is a sum of 64 table entries and needs no doublings
+const WINDOW_BITS: usize = 4;
+const WINDOWS: usize = 256 / WINDOW_BITS;
+const ENTRIES: usize = (1 << WINDOW_BITS) - 1;
+
+// Serialized form: every entry as x || y (big-endian), then the comb
+// table's entries in the same form, then SHA-256 of both
+const POINT_BYTES: usize = 64;
+const WINDOW_TABLE_BYTES: usize = WINDOWS * ENTRIES * POINT_BYTES;
+const PAYLOAD_BYTES: usize = WINDOW_TABLE_BYTES + COMB_BYTES;
+const TABLE_BYTES: usize = PAYLOAD_BYTES + 32;
+
+static GENERATOR_TABLE: OnceLock<GeneratorTable> = OnceLock::new();
+
+struct GeneratorTable {
+    // WINDOWS rows of ENTRIES affine points, row-major
+    points: Vec<Point>,
+}
+
+impl GeneratorTable {
+    fn compute() -> Self {
+        let mut multiples = Vec::with_capacity(WINDOWS * ENTRIES);
+        let mut base = JacobianPoint::from_affine(&Point::generator());
+        for _ in 0..WINDOWS {
+            let mut multiple = base;
+            for _ in 0..ENTRIES {
+                multiples.push(multiple);
+                multiple = multiple.add(&base);
+            }
+            // After the loop multiple = 16·base, the next window's base
+            base = multiple;
+        }
+        // One shared inversion instead of one per entry
+        JacobianPoint::normalize_all(&mut multiples);
+        let points = multiples
+            .iter()
+            .map(|p| Point { x: Some(p.x), y: Some(p.y) })
+            .collect();
+        GeneratorTable { points }
+    }
+
+    fn entry(&self, window: usize, digit: usize) -> &Point {
+        &self.points[window * ENTRIES + digit - 1]
+    }
+
+    fn mul(&self, scalar: &Scalar) -> Point {
+        let mut result = JacobianPoint::infinity();
+        for window in 0..WINDOWS {
+            let limb = scalar.value[window / 16];
+            let digit = ((limb >> ((window % 16) * WINDOW_BITS)) & 0xF) as usize;
+            if digit != 0 {
+                result = result.add(&JacobianPoint::from_affine(self.entry(window, digit)));
+            }
+        }
+        result.to_affine()
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(WINDOW_TABLE_BYTES);
+        for point in &self.points {
+            // Table entries are multiples of G below n, never infinity
+            bytes.extend_from_slice(&point.x.unwrap().to_bytes());
+            bytes.extend_from_slice(&point.y.unwrap().to_bytes());
+        }
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut points = Vec::with_capacity(WINDOWS * ENTRIES);
+        for chunk in bytes.chunks_exact(POINT_BYTES) {
+            let x = FieldElement::from_bytes(chunk[..32].try_into().unwrap())?;
+            let y = FieldElement::from_bytes(chunk[32..].try_into().unwrap())?;
+            points.push(Point::from_affine(x, y)?);
+        }
+
+        let table = GeneratorTable { points };
+        table.validate()?;
+        Ok(table)
+    }
+
+    // Check every entry is the expected multiple of G. This only needs
+    // Jacobian additions (no inversions), so it is much cheaper than
+    // recomputing the table.
+    fn validate(&self) -> Result<(), Error> {
+        let mut expected = JacobianPoint::from_affine(&Point::generator());
+        for window in 0..WINDOWS {
+            let base = expected;
+            for digit in 1..=ENTRIES {
+                if !expected.eq_affine(self.entry(window, digit)) {
+                    return Err(Error::InvalidEncoding);
+                }
+                expected = expected.add(&base);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn global_table() -> &'static GeneratorTable {
+    GENERATOR_TABLE.get_or_init(GeneratorTable::compute)
+}
+
+impl Point {
+    // k·G for public k using the precomputed generator table, computed on
+    // first use unless one was installed with load_generator_table. The
+    // fastest generator multiplication, 64 additions and no doublings, but
+    // variable time: zero digits skip their addition and each digit picks
+    // the table entry read. Signing uses mul_generator instead.
+    pub fn mul_generator_vartime(scalar: &Scalar) -> Point {
+        global_table().mul(scalar)
+    }
+}
+
+// Serialize both generator tables, the window table of mul_generator_vartime
+// and the comb of mul_generator (computing them if needed), so they can be
+// stored and loaded at startup instead of being recomputed
+pub fn serialize_generator_table() -> Vec<u8> {
+    let mut bytes = global_table().to_bytes();
+    bytes.extend_from_slice(&global_comb_table().to_bytes());
+    let checksum = sha256(&bytes);
+    bytes.extend_from_slice(&checksum);
+    bytes
+}
+
+// Parse serialize_generator_table output into its two tables, verifying the
+// checksum and every entry
+fn parse_tables(bytes: &[u8]) -> Result<(GeneratorTable, CombTable), Error> {
+    if bytes.len() != TABLE_BYTES {
+        return Err(Error::InvalidEncoding);
+    }
+    let (payload, checksum) = bytes.split_at(PAYLOAD_BYTES);
+    if sha256(payload) != checksum {
+        return Err(Error::InvalidChecksum);
+    }
+    let (window, comb) = payload.split_at(WINDOW_TABLE_BYTES);
+    Ok((GeneratorTable::from_bytes(window)?, CombTable::from_bytes(comb)?))
+}
+
+// Install both tables from serialize_generator_table output, so neither
+// mul_generator nor mul_generator_vartime computes one on first use. The
+// checksum and every entry are verified, and nothing is installed unless
+// all of it is valid; a table already in use is kept.
+pub fn load_generator_table(bytes: &[u8]) -> Result<(), Error> {
+    let (table, comb) = parse_tables(bytes)?;
+    // A second valid table is identical to the installed one, so losing the
+    // race is harmless
+    let _ = GENERATOR_TABLE.set(table);
+    install_comb_table(comb);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_scalars() -> Vec<Scalar> {
+        vec![
+            Scalar::new([1, 0, 0, 0]),
+            Scalar::new([0xF, 0, 0, 0]),
+            Scalar::new([0x0123456789ABCDEF, 0xFEDCBA9876543210, 0xDEADBEEFCAFEBABE, 0x7FFFFFFFFFFFFFFF]),
+            -Scalar::new([1, 0, 0, 0]),
+        ]
+    }
+
+    #[test]
+    fn test_mul_generator_vartime_matches_scalar_mul() {
+        for k in test_scalars() {
+            assert_eq!(Point::mul_generator_vartime(&k), Point::generator().scalar_mul(&k.value));
+        }
+        assert_eq!(Point::mul_generator_vartime(&Scalar::new([0, 0, 0, 0])), Point::infinity());
+    }
+
+    #[test]
+    fn test_serialized_table_round_trip() {
+        let bytes = serialize_generator_table();
+        assert_eq!(bytes.len(), TABLE_BYTES);
+
+        let (loaded, comb) = parse_tables(&bytes).unwrap();
+        for k in test_scalars() {
+            assert_eq!(loaded.mul(&k), Point::mul_generator_vartime(&k));
+            assert_eq!(comb.mul(&k), Point::mul_generator(&k));
+        }
+        assert_eq!(load_generator_table(&bytes), Ok(()));
+        for k in test_scalars() {
+            assert_eq!(Point::mul_generator(&k), Point::generator().scalar_mul(&k.value));
+        }
+    }
+
+    #[test]
+    fn test_corrupted_table_rejected() {
+        let bytes = serialize_generator_table();
+
+        assert_eq!(load_generator_table(&bytes[..bytes.len() - 1]), Err(Error::InvalidEncoding));
+
+        let mut corrupted = bytes.clone();
+        corrupted[100] ^= 1;
+        assert_eq!(load_generator_table(&corrupted), Err(Error::InvalidChecksum));
+
+        // Swapping two entries keeps every point on the curve; re-checksummed,
+        // it must still be caught by validation
+        let mut swapped = bytes[..PAYLOAD_BYTES].to_vec();
+        let (first, second) = swapped.split_at_mut(POINT_BYTES);
+        first.swap_with_slice(&mut second[..POINT_BYTES]);
+        let checksum = sha256(&swapped);
+        swapped.extend_from_slice(&checksum);
+        assert_eq!(load_generator_table(&swapped), Err(Error::InvalidEncoding));
+
+        // The same for two comb entries, here 2^32·G and G + 2^32·G
+        let mut swapped = bytes[..PAYLOAD_BYTES].to_vec();
+        let (first, second) = swapped[WINDOW_TABLE_BYTES + POINT_BYTES..].split_at_mut(POINT_BYTES);
+        first.swap_with_slice(&mut second[..POINT_BYTES]);
+        let checksum = sha256(&swapped);
+        swapped.extend_from_slice(&checksum);
+        assert_eq!(load_generator_table(&swapped), Err(Error::InvalidEncoding));
+    }
+}
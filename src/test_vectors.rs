@@ -0,0 +1,30 @@
+// Test assertions against canonical encodings. Comparing the big-endian
+// hex of a value, rather than its limbs, also catches results that were
+// left unreduced: those never match the canonical string.
+
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{hex, FieldElement, Point};
+
+// The one generator for randomized tests: ChaCha8 from a fixed seed, so a
+// failure reproduces. It also implements CryptoRng, so it can be passed to
+// key generation and blinding.
+pub(crate) fn test_rng(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
+
+#[track_caller]
+pub(crate) fn assert_fe_eq_hex(actual: FieldElement, expected_hex: &str) {
+    assert!(actual.is_valid(), "{:?} is not reduced below p", actual);
+    assert_eq!(hex::encode(&actual.to_bytes()), expected_hex.to_lowercase());
+}
+
+// expected_hex is a SEC1 encoding; its length picks compressed (33 bytes)
+// or uncompressed (65 bytes), and "00" stands for infinity
+#[track_caller]
+pub(crate) fn assert_point_eq_sec1(actual: Point, expected_hex: &str) {
+    assert!(actual.is_on_curve(), "{:?} is not on the curve", actual);
+    let compressed = expected_hex.len() != 130;
+    assert_eq!(hex::encode(&actual.serialize(compressed)), expected_hex.to_lowercase());
+}